@@ -1,6 +1,6 @@
-use crate::lib::read::{Error, Read};
-use crate::lib::write::Write;
+use rw_poc::{BufRead, Error, Read, Seek, Write};
 use std::fmt;
+use std::io::{ErrorKind, SeekFrom};
 
 pub struct TypeImplementingRead {
     data: &'static [u8],
@@ -28,6 +28,36 @@ impl Read for TypeImplementingRead {
     }
 }
 
+impl BufRead for TypeImplementingRead {
+    fn fill_buf(&mut self) -> Result<&[u8], Error> {
+        Ok(&self.data[self.i.min(self.data.len())..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.i += amt;
+    }
+}
+
+impl Seek for TypeImplementingRead {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, Error> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => (n, 0),
+            SeekFrom::End(n) => (self.data.len() as u64, n),
+            SeekFrom::Current(n) => (self.i as u64, n),
+        };
+        match base.checked_add_signed(offset) {
+            Some(n) => {
+                self.i = n as usize;
+                Ok(n)
+            }
+            None => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
 pub fn fun_req_read<R: Read>(mut reader: R) {
     let mut data = String::new();
     reader
@@ -71,3 +101,9 @@ pub fn fun_req_write<W: Write + fmt::Debug>(mut writer: W) {
     writer.write_all(&[0, 1, 2]).expect("Unable to write data");
     println!("{:?}", writer);
 }
+
+pub fn fun_req_seek<S: Seek>(mut seeker: S) {
+    let pos = seeker.seek(SeekFrom::End(-1)).expect("Unable to seek");
+    let len = seeker.stream_len().expect("Unable to get length");
+    println!("{} of {}", pos, len);
+}
@@ -0,0 +1,260 @@
+mod legacy;
+mod new;
+
+use rw_poc::{
+    AsyncReadCore, Base64Decoder, Base64Encoder, BitOrder, BitReader, BitWriter, Blocking,
+    Broadcast, BroadcastArray, BufReaderCore, BufWriterCore, Crc32, Cursor, FromBlocking,
+    FromStdIo, HashingReader, Inflate, IntoStdIo, LegacyRead, LegacySeek, LegacyWrite, Lz4Reader,
+    Lz4Writer, ReadAlloc, ReadCore, WriteCore, INFLATE_WINDOW_SIZE, LZ4_WINDOW_SIZE,
+};
+use std::fs::File;
+use std::io;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+
+fn main() {
+    read_to_readcore();
+    readcore_to_read();
+    write_to_writecore();
+    writecore_to_write();
+    seek_to_seekcore();
+    seekcore_to_seek();
+    bufread_to_bufreadcore();
+    readcore_to_bufreadcore();
+    writecore_to_bufwritecore();
+    cursor_to_readcore();
+    cursor_to_writecore();
+    asyncreadcore_to_readcore();
+    asyncwritecore_to_writecore();
+    readcore_to_primitives();
+    primitives_to_writecore();
+    varints_roundtrip();
+    copy_readcore_to_writecore();
+    bufreadcore_to_lines();
+    file_to_readcore();
+    socket_to_readcore();
+    readcore_to_std_read();
+    readcore_to_tee();
+    writecore_to_broadcast();
+    readcore_to_hashing();
+    writecore_to_base64();
+    readcore_to_inflate();
+    writecore_to_lz4();
+    bits_roundtrip();
+}
+
+fn read_to_readcore() {
+    let ty = legacy::TypeImplementingRead::default();
+    new::fun_req_read_ext(ty);
+}
+
+fn readcore_to_read() {
+    let ty = new::TypeImplementingReadCore::default();
+    let comp_layer = LegacyRead(ty);
+    legacy::fun_req_read(comp_layer);
+}
+
+fn write_to_writecore() {
+    let ty = legacy::TypeImplementingWrite::default();
+    new::fun_req_write(ty);
+}
+
+fn writecore_to_write() {
+    let ty = new::TypeImplementingWriteCore::default();
+    let comp_layer = LegacyWrite(ty);
+    legacy::fun_req_write(comp_layer);
+}
+
+fn seek_to_seekcore() {
+    let ty = legacy::TypeImplementingRead::default();
+    new::fun_req_seek(ty);
+}
+
+fn seekcore_to_seek() {
+    let ty = new::TypeImplementingReadCore::default();
+    let comp_layer = LegacySeek(ty);
+    legacy::fun_req_seek(comp_layer);
+}
+
+fn bufread_to_bufreadcore() {
+    let ty = legacy::TypeImplementingRead::default();
+    new::fun_req_buf_read(ty);
+}
+
+fn readcore_to_bufreadcore() {
+    let ty = new::TypeImplementingReadCore::default();
+    let buffered = BufReaderCore::<_, 2>::new(ty);
+    new::fun_req_buf_read(buffered);
+}
+
+fn writecore_to_bufwritecore() {
+    let ty = new::TypeImplementingWriteCore::default();
+    let buffered = BufWriterCore::<_, 4>::new(ty);
+    new::fun_req_write(buffered);
+}
+
+fn cursor_to_readcore() {
+    let ty = Cursor::new(b"ABC");
+    new::fun_req_read_ext(ty);
+}
+
+fn cursor_to_writecore() {
+    new::fun_req_write(Cursor::new([65, 66, 67]));
+    new::fun_req_write(Cursor::new(Vec::new()));
+}
+
+fn asyncreadcore_to_readcore() {
+    let ty = FromBlocking::new(new::TypeImplementingReadCore::default());
+    let comp_layer =
+        Blocking::new(ty.chain(FromBlocking::new(new::TypeImplementingReadCore::default())));
+    new::fun_req_read_ext(comp_layer);
+}
+
+fn asyncwritecore_to_writecore() {
+    let ty = FromBlocking::new(new::TypeImplementingWriteCore::default());
+    let comp_layer = Blocking::new(ty);
+    new::fun_req_write(comp_layer);
+}
+
+fn readcore_to_primitives() {
+    let ty = Cursor::new([1, 2, 0, 0, 128, 63, 255]);
+    new::fun_req_read_primitives(ty);
+}
+
+fn primitives_to_writecore() {
+    let ty = Cursor::new([0; 7]);
+    new::fun_req_write_primitives(ty);
+}
+
+fn varints_roundtrip() {
+    let mut ty = Cursor::new(Vec::new());
+    new::fun_req_write_varints(&mut ty);
+    ty.set_position(0);
+    new::fun_req_read_varints(&mut ty);
+}
+
+fn copy_readcore_to_writecore() {
+    let mut reader = new::TypeImplementingReadCore::default();
+    let mut writer = new::TypeImplementingWriteCore::default();
+    let copied = rw_poc::copy_buffered::<_, _, 2>(&mut reader, &mut writer);
+    println!("{:?} {:?}", copied, writer);
+
+    let mut reader = legacy::TypeImplementingRead::default();
+    let mut writer = legacy::TypeImplementingWrite::default();
+    let copied = rw_poc::copy(&mut reader, &mut writer);
+    println!("{:?} {:?}", copied, writer);
+}
+
+fn bufreadcore_to_lines() {
+    let ty = Cursor::new("GET / HTTP/1.1\r\nHost: localhost\r\nAccept: */*\r\n");
+    new::fun_req_read_lines(ty);
+}
+
+fn file_to_readcore() {
+    let name = format!("rw-poc-file_to_readcore-{}", std::process::id());
+    let path = std::env::temp_dir().join(name);
+    let file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .expect("Unable to create file");
+    new::fun_req_write(FromStdIo::new(file));
+    let file = File::open(&path).expect("Unable to open file");
+    new::fun_req_read_ext(FromStdIo::new(file));
+    std::fs::remove_file(&path).expect("Unable to remove file");
+}
+
+#[cfg(unix)]
+fn socket_to_readcore() {
+    let (first, second) = UnixStream::pair().expect("Unable to create socket pair");
+    new::fun_req_write(FromStdIo::new(first));
+    new::fun_req_read_ext(FromStdIo::new(second));
+}
+
+#[cfg(not(unix))]
+fn socket_to_readcore() {}
+
+fn readcore_to_std_read() {
+    let mut reader = IntoStdIo::new(new::TypeImplementingReadCore::default());
+    let mut writer = IntoStdIo::new(Cursor::new(Vec::new()));
+    let copied = io::copy(&mut reader, &mut writer);
+    println!("{:?} {:?}", copied, writer.into_inner());
+}
+
+fn readcore_to_tee() {
+    let mut tee = Cursor::new(b"ABC").tee(Cursor::new(Vec::new()));
+    let mut data = String::new();
+    tee.read_to_string(&mut data).expect("Unable to read data");
+    println!("{} {:?}", data, tee.writer());
+}
+
+fn writecore_to_broadcast() {
+    let mut broadcast = Broadcast::new(Cursor::new(Vec::new()), Cursor::new([0; 3]));
+    broadcast.write_all(b"ABC").expect("Unable to write data");
+    println!("{:?}", broadcast.get_ref());
+
+    let mut broadcast = BroadcastArray::new([Cursor::new([0; 3]), Cursor::new([0; 3])]);
+    broadcast.write_all(b"ABC").expect("Unable to write data");
+    println!("{:?}", broadcast.get_ref());
+}
+
+fn readcore_to_hashing() {
+    let mut reader = HashingReader::new(Cursor::new(b"ABC"), Crc32::new());
+    let mut data = String::new();
+    reader
+        .read_to_string(&mut data)
+        .expect("Unable to read data");
+    println!("{} {:08x}", data, reader.finish());
+}
+
+fn writecore_to_base64() {
+    let mut encoder = Base64Encoder::new(Cursor::new(Vec::new()));
+    encoder.write_all(b"ABC").expect("Unable to write data");
+    encoder.finish().expect("Unable to write data");
+    let encoded = encoder.into_inner().into_inner();
+    let mut decoder = Base64Decoder::new(Cursor::new(&encoded[..]));
+    let mut data = String::new();
+    decoder
+        .read_to_string(&mut data)
+        .expect("Unable to read data");
+    println!("{} {}", String::from_utf8_lossy(&encoded), data);
+}
+
+fn readcore_to_inflate() {
+    let compressed = include_bytes!("../../tests/data/fixed.zz");
+    let mut window = [0; INFLATE_WINDOW_SIZE];
+    let mut inflate = Inflate::zlib(Cursor::new(compressed), &mut window);
+    let mut data = String::new();
+    inflate
+        .read_to_string(&mut data)
+        .expect("Unable to read data");
+    print!("{}", data);
+}
+
+fn writecore_to_lz4() {
+    let mut writer = Lz4Writer::<_, 1024>::with_content_checksum(Cursor::new(Vec::new()));
+    writer
+        .write_all(b"ABC ABC ABC ABC")
+        .expect("Unable to write data");
+    writer.finish().expect("Unable to write data");
+    let compressed = writer.into_inner().into_inner();
+    let mut window = [0; LZ4_WINDOW_SIZE];
+    let mut reader = Lz4Reader::new(Cursor::new(&compressed[..]), &mut window);
+    let mut data = String::new();
+    reader
+        .read_to_string(&mut data)
+        .expect("Unable to read data");
+    println!("{} {}", compressed.len(), data);
+}
+
+fn bits_roundtrip() {
+    let mut writer = BitWriter::new(Cursor::new(Vec::new()), BitOrder::MsbFirst);
+    writer.write_bits(5, 3).expect("Unable to write data");
+    writer.write_bool(true).expect("Unable to write data");
+    writer.flush().expect("Unable to write data");
+    let data = writer.into_inner().into_inner();
+    let mut reader = BitReader::new(Cursor::new(&data[..]), BitOrder::MsbFirst);
+    let value = reader.read_bits(3).expect("Unable to read data");
+    let flag = reader.read_bool().expect("Unable to read data");
+    println!("{:?} {} {}", data, value, flag);
+}
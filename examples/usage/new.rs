@@ -0,0 +1,275 @@
+use core::fmt::Debug;
+use core::num::NonZeroUsize;
+use rw_poc::{
+    BigEndian, BufReadAlloc, BufReadCore, ClassifyError, CoreErrorKind, FillRes, FormatterError,
+    InvalidUtf8, InvalidVarint, OpRes, OutOfBounds, ReadAlloc, ReadCore, ReadCoreExt, SeekCore,
+    SeekFrom, UnexpectedEndOfFile, WouldBlock, WriteCore, WriteCoreExt,
+};
+
+#[derive(Debug)]
+pub enum TypeImplementingCoreError {
+    InvalidUtf8,
+    UnexpectedEof,
+    FormatterError,
+    OutOfBounds,
+    WouldBlock,
+}
+
+impl From<InvalidUtf8> for TypeImplementingCoreError {
+    fn from(_: InvalidUtf8) -> Self {
+        Self::InvalidUtf8
+    }
+}
+
+impl From<UnexpectedEndOfFile> for TypeImplementingCoreError {
+    fn from(_: UnexpectedEndOfFile) -> Self {
+        Self::UnexpectedEof
+    }
+}
+
+impl From<OutOfBounds> for TypeImplementingCoreError {
+    fn from(_: OutOfBounds) -> Self {
+        Self::OutOfBounds
+    }
+}
+
+impl From<FormatterError> for TypeImplementingCoreError {
+    fn from(_: FormatterError) -> Self {
+        Self::FormatterError
+    }
+}
+
+impl From<WouldBlock> for TypeImplementingCoreError {
+    fn from(_: WouldBlock) -> Self {
+        Self::WouldBlock
+    }
+}
+
+impl ClassifyError for TypeImplementingCoreError {
+    fn kind(&self) -> CoreErrorKind {
+        match self {
+            Self::InvalidUtf8 => CoreErrorKind::InvalidData,
+            Self::UnexpectedEof => CoreErrorKind::UnexpectedEof,
+            Self::FormatterError => CoreErrorKind::Other,
+            Self::OutOfBounds => CoreErrorKind::InvalidInput,
+            Self::WouldBlock => CoreErrorKind::WouldBlock,
+        }
+    }
+}
+
+pub struct TypeImplementingReadCore {
+    data: &'static [u8],
+    i: usize,
+}
+
+impl Default for TypeImplementingReadCore {
+    fn default() -> Self {
+        Self {
+            data: &[65, 66, 67],
+            i: 0,
+        }
+    }
+}
+
+impl ReadCore for TypeImplementingReadCore {
+    type Err = TypeImplementingCoreError;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
+        let mut written = 0;
+        for (des, src) in buf.iter_mut().zip(self.data.iter().skip(self.i)) {
+            *des = *src;
+            written += 1;
+        }
+        self.i += written;
+        Ok(NonZeroUsize::new(written).map_or(OpRes::Eof, |v| {
+            if v.get() == buf.len() {
+                OpRes::Completly(v)
+            } else {
+                OpRes::Partial(v)
+            }
+        }))
+    }
+}
+
+impl SeekCore for TypeImplementingReadCore {
+    type Err = TypeImplementingCoreError;
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Err> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => (n, 0),
+            SeekFrom::End(n) => (self.data.len() as u64, n),
+            SeekFrom::Current(n) => (self.i as u64, n),
+        };
+        let pos = base.checked_add_signed(offset).ok_or(OutOfBounds)?;
+        self.i = pos as usize;
+        Ok(pos)
+    }
+}
+
+pub fn fun_req_read_ext<
+    E: Debug + From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock>,
+    R: ReadAlloc + ReadCore<Err = E>,
+>(
+    mut reader: R,
+) {
+    let mut data = String::new();
+    reader
+        .read_to_string(&mut data)
+        .expect("Unable to read data");
+    println!("{}", data);
+}
+
+pub fn fun_req_buf_read<
+    E: Debug + From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock>,
+    R: BufReadCore + ReadCore<Err = E>,
+>(
+    mut reader: R,
+) {
+    let mut data = Vec::new();
+    loop {
+        let amt = match reader.fill_buf().expect("Unable to fill buffer") {
+            FillRes::Data(buf) => {
+                data.push(buf.to_vec());
+                buf.len()
+            }
+            FillRes::Retry | FillRes::WouldBlock => 0,
+            FillRes::Eof => break,
+        };
+        reader.consume(amt);
+    }
+    println!("{:?}", data);
+}
+
+pub fn fun_req_read_lines<
+    E: Debug + From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock>,
+    R: BufReadCore + ReadCore<Err = E>,
+>(
+    mut reader: R,
+) {
+    let mut header = String::new();
+    reader.read_line(&mut header).expect("Unable to read line");
+    let mut field = Vec::new();
+    reader
+        .read_until(b':', &mut field)
+        .expect("Unable to read field");
+    for line in reader.lines() {
+        println!(
+            "{:?} {:?} {:?}",
+            header,
+            field,
+            line.expect("Unable to read line")
+        );
+    }
+}
+
+pub fn fun_req_read_primitives<
+    E: Debug + From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock>,
+    R: ReadCore<Err = E>,
+>(
+    mut reader: R,
+) {
+    let a = reader.read_u16_be().expect("Unable to read u16");
+    let b = reader.read_f32_le().expect("Unable to read f32");
+    let c = reader
+        .read_int::<i8, BigEndian>()
+        .expect("Unable to read i8");
+    let d = reader.read_u8();
+    println!("{} {} {} {:?}", a, b, c, d);
+}
+
+#[derive(Debug)]
+pub struct TypeImplementingWriteCore {
+    data: [u8; 3],
+    i: usize,
+}
+
+impl Default for TypeImplementingWriteCore {
+    fn default() -> Self {
+        Self {
+            data: [65, 66, 67],
+            i: 0,
+        }
+    }
+}
+
+impl WriteCore for TypeImplementingWriteCore {
+    type Err = TypeImplementingCoreError;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        let mut written = 0;
+        for (des, src) in self.data.iter_mut().skip(self.i).zip(buf) {
+            *des = *src;
+            written += 1;
+        }
+        self.i += written;
+        Ok(NonZeroUsize::new(written).map_or(OpRes::Eof, |v| {
+            if v.get() == buf.len() {
+                OpRes::Completly(v)
+            } else {
+                OpRes::Partial(v)
+            }
+        }))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
+pub fn fun_req_write<
+    E: Debug + From<FormatterError> + From<UnexpectedEndOfFile> + From<WouldBlock>,
+    W: Debug + WriteCore<Err = E>,
+>(
+    mut writer: W,
+) {
+    writer.write_all(&[0, 1, 2]).expect("Unable to write data");
+    println!("{:?}", writer);
+}
+
+pub fn fun_req_seek<E: Debug + From<OutOfBounds>, S: SeekCore<Err = E>>(mut seeker: S) {
+    let pos = seeker.seek(SeekFrom::End(-1)).expect("Unable to seek");
+    let len = seeker.stream_len().expect("Unable to get length");
+    println!("{} of {}", pos, len);
+}
+
+pub fn fun_req_write_primitives<
+    E: Debug + From<FormatterError> + From<UnexpectedEndOfFile> + From<WouldBlock>,
+    W: Debug + WriteCore<Err = E>,
+>(
+    mut writer: W,
+) {
+    writer.write_u16_be(258).expect("Unable to write u16");
+    writer.write_f32_le(1.0).expect("Unable to write f32");
+    writer
+        .write_int::<i8, BigEndian>(-1)
+        .expect("Unable to write i8");
+    let res = writer.write_u8(0);
+    println!("{:?} {:?}", writer, res);
+}
+
+pub fn fun_req_read_varints<
+    E: Debug + From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock> + From<InvalidVarint>,
+    R: ReadCore<Err = E>,
+>(
+    reader: &mut R,
+) {
+    let a = reader.read_uleb128::<u32>().expect("Unable to read u32");
+    let b = reader.read_sleb128::<i64>().expect("Unable to read i64");
+    let c = reader.read_uleb128::<u8>();
+    println!("{} {} {:?}", a, b, c);
+}
+
+pub fn fun_req_write_varints<
+    E: Debug + From<FormatterError> + From<UnexpectedEndOfFile> + From<WouldBlock>,
+    W: WriteCore<Err = E>,
+>(
+    writer: &mut W,
+) {
+    writer
+        .write_uleb128(624_485u32)
+        .expect("Unable to write u32");
+    writer
+        .write_sleb128(-123_456i64)
+        .expect("Unable to write i64");
+    writer.write_uleb128(256u16).expect("Unable to write u16");
+}
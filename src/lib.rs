@@ -0,0 +1,70 @@
+#![no_std]
+#![cfg_attr(not(feature = "stable"), feature(specialization))]
+#![cfg_attr(not(feature = "stable"), allow(incomplete_features))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+/// Marks a method of a blanket impl as specializable. With the `stable`
+/// feature, which builds without `specialization`, the method is final.
+#[cfg(not(feature = "stable"))]
+macro_rules! default_fn {
+    ($(#[$attr:meta])* fn $($rest:tt)*) => {
+        $(#[$attr])* default fn $($rest)*
+    };
+}
+
+#[cfg(feature = "stable")]
+macro_rules! default_fn {
+    ($($item:tt)*) => {
+        $($item)*
+    };
+}
+
+mod lib {
+    #[cfg(feature = "alloc")]
+    pub mod alloc;
+    pub mod bits;
+    pub mod codec;
+    pub mod core;
+    pub mod digest;
+    pub mod inflate;
+    #[cfg(feature = "std")]
+    pub mod legacy;
+    pub mod lz4;
+    #[cfg(feature = "std")]
+    pub mod os;
+    #[cfg(feature = "std")]
+    pub mod read;
+    #[cfg(feature = "std")]
+    pub mod seek;
+    #[cfg(feature = "std")]
+    pub mod std;
+    #[cfg(feature = "std")]
+    pub mod write;
+}
+
+pub use crate::lib::bits::{BitOrder, BitReader, BitWriter};
+pub use crate::lib::codec::{Base64Decoder, Base64Encoder, HexDecoder, HexEncoder};
+pub use crate::lib::core::*;
+pub use crate::lib::digest::{
+    Adler32, Crc16, Crc32, Digest, HashingReader, HashingWriter, Sha256, Xxh32,
+};
+pub use crate::lib::inflate::{Inflate, InflateFormat, INFLATE_WINDOW_SIZE};
+pub use crate::lib::lz4::{Lz4Reader, Lz4Writer, LZ4_WINDOW_SIZE};
+
+#[cfg(feature = "alloc")]
+pub use crate::lib::alloc::{BufReadAlloc, Lines, ReadAlloc, Split};
+
+#[cfg(feature = "std")]
+pub use crate::lib::legacy::{LegacyError, LegacyRead, LegacySeek, LegacyWrite};
+#[cfg(feature = "std")]
+pub use crate::lib::read::{BufRead, Error, Read};
+#[cfg(feature = "std")]
+pub use crate::lib::seek::Seek;
+#[cfg(feature = "std")]
+pub use crate::lib::std::{FromStdIo, IntoStdIo, ReadStd, WriteStd};
+#[cfg(feature = "std")]
+pub use crate::lib::write::Write;
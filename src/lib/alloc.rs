@@ -1,7 +1,14 @@
-use super::core::{InvalidUtf8, OpRes, ReadCore, Take, UnexpectedEndOfFile};
+#[cfg(not(feature = "stable"))]
+use super::core::Take;
+use super::core::{
+    BufReadCore, Cursor, CursorError, FillRes, InvalidUtf8, OpRes, OutOfBounds, ReadBufCore,
+    ReadCore, UnexpectedEndOfFile, WouldBlock, WriteCore,
+};
 use alloc::string::String;
 use alloc::vec::Vec;
 use core::cmp;
+use core::convert::TryFrom;
+use core::num::NonZeroUsize;
 use core::str;
 
 pub trait ReadAlloc: ReadCore {
@@ -14,18 +21,22 @@ impl<T: ?Sized> ReadAlloc for T
 where
     T: ReadCore,
 {
-    default fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<(), Self::Err> {
-        Self::read_to_end(self, buf)
+    default_fn! {
+        fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<(), Self::Err> {
+            read_to_end(self, buf)
+        }
     }
 
-    default fn read_to_string(&mut self, buf: &mut String) -> Result<(), Self::Err> {
-        append_to_string(buf, |b| read_to_end(self, b))
+    default_fn! {
+        fn read_to_string(&mut self, buf: &mut String) -> Result<(), Self::Err> {
+            append_to_string(buf, |b| read_to_end(self, b))
+        }
     }
 }
 
 fn read_to_end<E, R: ReadCore<Err = E> + ?Sized>(r: &mut R, buf: &mut Vec<u8>) -> Result<(), E>
 where
-    E: From<InvalidUtf8> + From<UnexpectedEndOfFile>,
+    E: From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock>,
 {
     read_to_end_with_reservation(r, buf, |_| 32)
 }
@@ -36,28 +47,34 @@ fn read_to_end_with_reservation<E, R, F>(
     mut reservation_size: F,
 ) -> Result<(), E>
 where
-    E: From<InvalidUtf8> + From<UnexpectedEndOfFile>,
+    E: From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock>,
     R: ReadCore<Err = E> + ?Sized,
     F: FnMut(&R) -> usize,
 {
-    let mut g = Guard {
-        len: buf.len(),
-        buf,
-    };
+    // Bytes in the spare capacity of `buf` which were initialized by an
+    // earlier iteration but not filled with data.
+    let mut initialized = 0;
     loop {
-        if g.len == g.buf.len() {
-            unsafe {
-                g.buf.reserve(reservation_size(r));
-                let capacity = g.buf.capacity();
-                g.buf.set_len(capacity);
-                r.initializer().initialize(&mut g.buf[g.len..]);
-            }
+        if buf.len() == buf.capacity() {
+            buf.reserve(reservation_size(r));
+        }
+
+        let mut read_buf = ReadBufCore::uninit(buf.spare_capacity_mut());
+        unsafe {
+            read_buf.assume_init(initialized);
+        }
+        let res = r.read_buf(&mut read_buf)?;
+        let filled = read_buf.filled().len();
+        initialized = read_buf.initialized_len() - filled;
+        unsafe {
+            // The filled bytes are initialized by `ReadBufCore`
+            buf.set_len(buf.len() + filled);
         }
 
-        match r.read(&mut g.buf[g.len..])? {
+        match res {
             OpRes::Eof => break,
-            OpRes::Retry => {}
-            OpRes::Partial(n) | OpRes::Completly(n) => g.len += n.get(),
+            OpRes::WouldBlock => return Err(E::from(WouldBlock)),
+            _ => {}
         }
     }
     Ok(())
@@ -96,11 +113,186 @@ where
     }
 }
 
+#[cfg(not(feature = "stable"))]
 impl<E, T: ReadCore<Err = E>> ReadAlloc for Take<T>
 where
-    E: From<InvalidUtf8> + From<UnexpectedEndOfFile>,
+    E: From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock>,
 {
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<(), E> {
         read_to_end_with_reservation(self, buf, |self_| cmp::min(self_.limit(), 32) as usize)
     }
 }
+
+pub trait BufReadAlloc: BufReadCore {
+    /// Reads until `byte` or the end of file and appends the data, including
+    /// the delimiter, to `buf`. Returns the number of bytes read, `0` on Eof.
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize, Self::Err>;
+
+    /// Like `read_until` with `b'\n'`. The data read is appended to `buf`
+    /// only if it is valid UTF-8.
+    fn read_line(&mut self, buf: &mut String) -> Result<usize, Self::Err>;
+
+    fn split(self, byte: u8) -> Split<Self>
+    where
+        Self: Sized;
+
+    fn lines(self) -> Lines<Self>
+    where
+        Self: Sized;
+}
+
+impl<T: ?Sized> BufReadAlloc for T
+where
+    T: BufReadCore,
+{
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize, Self::Err> {
+        read_until(self, byte, buf)
+    }
+
+    fn read_line(&mut self, buf: &mut String) -> Result<usize, Self::Err> {
+        let mut read = 0;
+        append_to_string(buf, |b| read_until(self, b'\n', b).map(|n| read = n))?;
+        Ok(read)
+    }
+
+    fn split(self, byte: u8) -> Split<Self>
+    where
+        Self: Sized,
+    {
+        Split { inner: self, byte }
+    }
+
+    fn lines(self) -> Lines<Self>
+    where
+        Self: Sized,
+    {
+        Lines { inner: self }
+    }
+}
+
+fn read_until<R: BufReadCore + ?Sized>(
+    r: &mut R,
+    delim: u8,
+    buf: &mut Vec<u8>,
+) -> Result<usize, R::Err> {
+    let mut read = 0;
+    loop {
+        let (done, used) = match r.fill_buf()? {
+            FillRes::Data(available) => match available.iter().position(|b| *b == delim) {
+                Some(i) => {
+                    buf.extend_from_slice(&available[..=i]);
+                    (true, i + 1)
+                }
+                None => {
+                    buf.extend_from_slice(available);
+                    (false, available.len())
+                }
+            },
+            FillRes::Retry => continue,
+            FillRes::WouldBlock => return Err(R::Err::from(WouldBlock)),
+            FillRes::Eof => return Ok(read),
+        };
+        r.consume(used);
+        read += used;
+        if done {
+            return Ok(read);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Split<B> {
+    inner: B,
+    byte: u8,
+}
+
+impl<B: BufReadCore> Iterator for Split<B> {
+    type Item = Result<Vec<u8>, B::Err>;
+
+    fn next(&mut self) -> Option<Result<Vec<u8>, B::Err>> {
+        let mut buf = Vec::new();
+        match self.inner.read_until(self.byte, &mut buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                if buf[buf.len() - 1] == self.byte {
+                    buf.pop();
+                }
+                Some(Ok(buf))
+            }
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Lines<B> {
+    inner: B,
+}
+
+impl<B: BufReadCore> Iterator for Lines<B> {
+    type Item = Result<String, B::Err>;
+
+    fn next(&mut self) -> Option<Result<String, B::Err>> {
+        let mut buf = String::new();
+        match self.inner.read_line(&mut buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                if buf.ends_with('\n') {
+                    buf.pop();
+                    if buf.ends_with('\r') {
+                        buf.pop();
+                    }
+                }
+                Some(Ok(buf))
+            }
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// Writes behind the end of the vector first fill the gap with zeros.
+/// Positions which do not fit into `usize` are out of bounds.
+fn vec_write(pos_mut: &mut u64, vec: &mut Vec<u8>, buf: &[u8]) -> Result<OpRes, CursorError> {
+    if buf.is_empty() {
+        return Ok(OpRes::Empty);
+    }
+    let pos = usize::try_from(*pos_mut).map_err(|_| OutOfBounds)?;
+    if vec.len() < pos {
+        vec.resize(pos, 0);
+    }
+    let overlap = cmp::min(vec.len() - pos, buf.len());
+    vec[pos..pos + overlap].copy_from_slice(&buf[..overlap]);
+    vec.extend_from_slice(&buf[overlap..]);
+    *pos_mut += buf.len() as u64;
+    Ok(NonZeroUsize::new(buf.len()).map_or(OpRes::Empty, OpRes::Completly))
+}
+
+impl WriteCore for Cursor<Vec<u8>> {
+    type Err = CursorError;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        let mut pos = self.position();
+        let res = vec_write(&mut pos, self.get_mut(), buf)?;
+        self.set_position(pos);
+        Ok(res)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
+impl WriteCore for Cursor<&mut Vec<u8>> {
+    type Err = CursorError;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        let mut pos = self.position();
+        let res = vec_write(&mut pos, self.get_mut(), buf)?;
+        self.set_position(pos);
+        Ok(res)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
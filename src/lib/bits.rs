@@ -0,0 +1,196 @@
+use super::core::{OpRes, ReadCore, UnexpectedEndOfFile, WouldBlock, WriteCore};
+
+/// Order in which `BitReader` and `BitWriter` pack bits into bytes.
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+pub enum BitOrder {
+    /// The first bit is the most significant bit of a byte and fields are
+    /// stored with their most significant bit first.
+    MsbFirst,
+    /// The first bit is the least significant bit of a byte and fields are
+    /// stored with their least significant bit first.
+    LsbFirst,
+}
+
+fn bit_mask(n: u32) -> u128 {
+    (1 << n) - 1
+}
+
+/// Reader for fields of up to 64 bits. Bytes are read from `inner` only when
+/// needed, so after `align_to_byte` the remaining data can be read from
+/// `inner` directly.
+pub struct BitReader<R> {
+    inner: R,
+    order: BitOrder,
+    bits: u128,
+    count: u32,
+}
+
+impl<R: ReadCore> BitReader<R> {
+    pub fn new(inner: R, order: BitOrder) -> Self {
+        Self {
+            inner,
+            order,
+            bits: 0,
+            count: 0,
+        }
+    }
+
+    pub fn order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// Reads a field of `n` bits. Bits which were read before an error are
+    /// kept, so a retry continues where the last attempt stopped.
+    ///
+    /// Panics if `n` is larger than 64.
+    pub fn read_bits(&mut self, n: u32) -> Result<u64, R::Err> {
+        assert!(n <= 64, "cannot read more than 64 bits at once");
+        while self.count < n {
+            let mut byte = [0];
+            match self.inner.read(&mut byte)? {
+                OpRes::Partial(_) | OpRes::Completly(_) => {}
+                OpRes::Retry => continue,
+                OpRes::Eof | OpRes::Empty => return Err(R::Err::from(UnexpectedEndOfFile)),
+                OpRes::WouldBlock => return Err(R::Err::from(WouldBlock)),
+            }
+            match self.order {
+                BitOrder::MsbFirst => self.bits = self.bits << 8 | u128::from(byte[0]),
+                BitOrder::LsbFirst => self.bits |= u128::from(byte[0]) << self.count,
+            }
+            self.count += 8;
+        }
+        Ok(self.take(n) as u64)
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, R::Err> {
+        self.read_bits(1).map(|bit| bit == 1)
+    }
+
+    /// Drops the bits up to the next byte boundary.
+    pub fn align_to_byte(&mut self) {
+        self.take(self.count % 8);
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader. Bits which were not read yet are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn take(&mut self, n: u32) -> u128 {
+        let value = match self.order {
+            BitOrder::MsbFirst => (self.bits >> (self.count - n)) & bit_mask(n),
+            BitOrder::LsbFirst => {
+                let value = self.bits & bit_mask(n);
+                self.bits >>= n;
+                value
+            }
+        };
+        self.count -= n;
+        self.bits &= bit_mask(self.count);
+        value
+    }
+}
+
+/// Writer for fields of up to 64 bits. Completed bytes are written to
+/// `inner` by the next call to `write_bits` or `flush`.
+pub struct BitWriter<W> {
+    inner: W,
+    order: BitOrder,
+    bits: u128,
+    count: u32,
+}
+
+impl<W: WriteCore> BitWriter<W> {
+    pub fn new(inner: W, order: BitOrder) -> Self {
+        Self {
+            inner,
+            order,
+            bits: 0,
+            count: 0,
+        }
+    }
+
+    pub fn order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// Writes the lowest `n` bits of `value`. The bits are only taken if no
+    /// error occurs.
+    ///
+    /// Panics if `n` is larger than 64.
+    pub fn write_bits(&mut self, value: u64, n: u32) -> Result<(), W::Err> {
+        assert!(n <= 64, "cannot write more than 64 bits at once");
+        self.write_bytes()?;
+        let value = u128::from(value) & bit_mask(n);
+        match self.order {
+            BitOrder::MsbFirst => self.bits = self.bits << n | value,
+            BitOrder::LsbFirst => self.bits |= value << self.count,
+        }
+        self.count += n;
+        Ok(())
+    }
+
+    pub fn write_bool(&mut self, value: bool) -> Result<(), W::Err> {
+        self.write_bits(u64::from(value), 1)
+    }
+
+    /// Pads the last byte with zero bits, writes all bytes and flushes
+    /// `inner`.
+    pub fn flush(&mut self) -> Result<(), W::Err> {
+        let padding = (8 - self.count % 8) % 8;
+        if let BitOrder::MsbFirst = self.order {
+            self.bits <<= padding;
+        }
+        self.count += padding;
+        self.write_bytes()?;
+        self.inner.flush()
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the inner writer. Bits which were not written yet are lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes all completed bytes. Bytes which were written are removed even
+    /// if an error occurs.
+    fn write_bytes(&mut self) -> Result<(), W::Err> {
+        while self.count >= 8 {
+            let len = (self.count / 8) as usize;
+            let mut bytes = [0; 16];
+            for (i, byte) in bytes[..len].iter_mut().enumerate() {
+                *byte = match self.order {
+                    BitOrder::MsbFirst => (self.bits >> (self.count - 8 * (i as u32 + 1))) as u8,
+                    BitOrder::LsbFirst => (self.bits >> (8 * i)) as u8,
+                };
+            }
+            let n = match self.inner.write(&bytes[..len])? {
+                OpRes::Partial(n) | OpRes::Completly(n) => n.get() as u32,
+                OpRes::Retry => continue,
+                OpRes::Eof | OpRes::Empty => return Err(W::Err::from(UnexpectedEndOfFile)),
+                OpRes::WouldBlock => return Err(W::Err::from(WouldBlock)),
+            };
+            if let BitOrder::LsbFirst = self.order {
+                self.bits >>= 8 * n;
+            }
+            self.count -= 8 * n;
+            self.bits &= bit_mask(self.count);
+        }
+        Ok(())
+    }
+}
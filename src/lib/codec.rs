@@ -0,0 +1,380 @@
+use super::core::{
+    written, InvalidEncoding, OpRes, ReadCore, UnexpectedEndOfFile, WouldBlock, WriteCore,
+};
+use core::cmp;
+use core::num::NonZeroUsize;
+
+pub(crate) const CODEC_BUF_SIZE: usize = 64;
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Data produced by an encoder or decoder which was not passed on yet.
+struct CodecBuf {
+    buf: [u8; CODEC_BUF_SIZE],
+    pos: usize,
+    len: usize,
+}
+
+impl CodecBuf {
+    fn new() -> Self {
+        Self {
+            buf: [0; CODEC_BUF_SIZE],
+            pos: 0,
+            len: 0,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos == self.len
+    }
+
+    fn push(&mut self, byte: u8) {
+        self.buf[self.len] = byte;
+        self.len += 1;
+    }
+
+    fn clear(&mut self) {
+        self.pos = 0;
+        self.len = 0;
+    }
+
+    /// Writes the buffered data to `writer`. Bytes which were written are
+    /// removed from the buffer even if an error occurs.
+    fn drain<W: WriteCore>(&mut self, writer: &mut W) -> Result<(), W::Err> {
+        while !self.is_empty() {
+            match writer.write(&self.buf[self.pos..self.len])? {
+                OpRes::Retry => {}
+                OpRes::Partial(n) | OpRes::Completly(n) => self.pos += n.get(),
+                OpRes::Eof | OpRes::Empty => return Err(W::Err::from(UnexpectedEndOfFile)),
+                OpRes::WouldBlock => return Err(W::Err::from(WouldBlock)),
+            }
+        }
+        self.clear();
+        Ok(())
+    }
+
+    fn copy_to(&mut self, buf: &mut [u8]) -> OpRes {
+        let n = cmp::min(self.len - self.pos, buf.len());
+        buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        match NonZeroUsize::new(n) {
+            None => OpRes::Eof,
+            Some(n) if n.get() == buf.len() => OpRes::Completly(n),
+            Some(n) => OpRes::Partial(n),
+        }
+    }
+}
+
+/// Writer which writes every byte as two lowercase hex digits into `inner`.
+/// Encoded data is buffered, call `flush` or `finish` to write it out.
+pub struct HexEncoder<W> {
+    inner: W,
+    out: CodecBuf,
+}
+
+impl<W: WriteCore> HexEncoder<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            out: CodecBuf::new(),
+        }
+    }
+
+    /// Writes all buffered data and flushes `inner`.
+    pub fn finish(&mut self) -> Result<(), W::Err> {
+        self.flush()
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the inner writer. Data which was not written yet is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: WriteCore> WriteCore for HexEncoder<W> {
+    type Err = W::Err;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        if buf.is_empty() {
+            return Ok(OpRes::Empty);
+        }
+        self.out.drain(&mut self.inner)?;
+        let n = cmp::min(buf.len(), CODEC_BUF_SIZE / 2);
+        for b in &buf[..n] {
+            self.out.push(HEX_DIGITS[usize::from(b >> 4)]);
+            self.out.push(HEX_DIGITS[usize::from(b & 0xF)]);
+        }
+        Ok(written(n, buf.len()))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        self.out.drain(&mut self.inner)?;
+        self.inner.flush()
+    }
+}
+
+/// Writer which base64 encodes all data into `inner`, using the standard
+/// alphabet of RFC 4648. Encoded data is buffered, call `finish` at the end
+/// of the data to write it out together with the padding.
+pub struct Base64Encoder<W> {
+    inner: W,
+    out: CodecBuf,
+    // Bytes which do not form a complete group of three yet
+    carry: [u8; 3],
+    carry_len: usize,
+}
+
+impl<W: WriteCore> Base64Encoder<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            out: CodecBuf::new(),
+            carry: [0; 3],
+            carry_len: 0,
+        }
+    }
+
+    /// Encodes the remaining bytes with padding, writes all buffered data and
+    /// flushes `inner`. Data written afterwards starts a new encoding.
+    pub fn finish(&mut self) -> Result<(), W::Err> {
+        if self.carry_len > 0 {
+            self.out.drain(&mut self.inner)?;
+            self.carry[self.carry_len..].fill(0);
+            self.encode_carry();
+            let padding = 3 - self.carry_len;
+            self.out.len -= padding;
+            for _ in 0..padding {
+                self.out.push(b'=');
+            }
+            self.carry_len = 0;
+        }
+        self.flush()
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the inner writer. Data which was not written yet is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn encode_carry(&mut self) {
+        let [b0, b1, b2] = self.carry;
+        for sextet in [
+            b0 >> 2,
+            (b0 & 0x3) << 4 | b1 >> 4,
+            (b1 & 0xF) << 2 | b2 >> 6,
+            b2 & 0x3F,
+        ] {
+            self.out.push(BASE64_ALPHABET[usize::from(sextet)]);
+        }
+    }
+}
+
+impl<W: WriteCore> WriteCore for Base64Encoder<W> {
+    type Err = W::Err;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        if buf.is_empty() {
+            return Ok(OpRes::Empty);
+        }
+        self.out.drain(&mut self.inner)?;
+        let mut n = 0;
+        while n < buf.len() && self.out.len + 4 <= CODEC_BUF_SIZE {
+            self.carry[self.carry_len] = buf[n];
+            self.carry_len += 1;
+            n += 1;
+            if self.carry_len == 3 {
+                self.encode_carry();
+                self.carry_len = 0;
+            }
+        }
+        Ok(written(n, buf.len()))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        self.out.drain(&mut self.inner)?;
+        self.inner.flush()
+    }
+}
+
+/// Reader which decodes hex digits read from `inner`. Upper- and lowercase
+/// digits are accepted and ASCII whitespace is skipped. Any other input, or an
+/// odd number of digits, fails with `InvalidEncoding`.
+pub struct HexDecoder<R> {
+    inner: R,
+    out: CodecBuf,
+    high: Option<u8>,
+}
+
+impl<R: ReadCore> HexDecoder<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            out: CodecBuf::new(),
+            high: None,
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: ReadCore> ReadCore for HexDecoder<R>
+where
+    R::Err: From<InvalidEncoding>,
+{
+    type Err = R::Err;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
+        if buf.is_empty() {
+            return Ok(OpRes::Empty);
+        }
+        while self.out.is_empty() {
+            let mut input = [0; CODEC_BUF_SIZE];
+            let n = match self.inner.read(&mut input)? {
+                OpRes::Partial(n) | OpRes::Completly(n) => n.get(),
+                OpRes::Eof if self.high.is_some() => return Err(R::Err::from(InvalidEncoding)),
+                res => return Ok(res),
+            };
+            self.out.clear();
+            for c in &input[..n] {
+                let digit = match c {
+                    b'0'..=b'9' => c - b'0',
+                    b'a'..=b'f' => c - b'a' + 10,
+                    b'A'..=b'F' => c - b'A' + 10,
+                    c if c.is_ascii_whitespace() => continue,
+                    _ => return Err(R::Err::from(InvalidEncoding)),
+                };
+                match self.high.take() {
+                    Some(high) => self.out.push(high << 4 | digit),
+                    None => self.high = Some(digit),
+                }
+            }
+        }
+        Ok(self.out.copy_to(buf))
+    }
+}
+
+/// Reader which decodes base64 read from `inner`, using the standard alphabet
+/// of RFC 4648. Padding is required and ASCII whitespace is skipped. Any other
+/// input, including data after the padding, fails with `InvalidEncoding`.
+pub struct Base64Decoder<R> {
+    inner: R,
+    out: CodecBuf,
+    // Sextets of the current group of four characters
+    acc: u32,
+    acc_len: u8,
+    padding: u8,
+}
+
+impl<R: ReadCore> Base64Decoder<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            out: CodecBuf::new(),
+            acc: 0,
+            acc_len: 0,
+            padding: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn decode(&mut self, c: u8) -> Result<(), InvalidEncoding> {
+        let sextet = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' if self.acc_len >= 2 && self.acc_len + self.padding < 4 => {
+                self.padding += 1;
+                if self.acc_len + self.padding == 4 {
+                    let bytes = (self.acc << (6 * u32::from(self.padding))).to_be_bytes();
+                    for b in &bytes[1..usize::from(self.acc_len)] {
+                        self.out.push(*b);
+                    }
+                }
+                return Ok(());
+            }
+            c if c.is_ascii_whitespace() => return Ok(()),
+            _ => return Err(InvalidEncoding),
+        };
+        if self.padding > 0 {
+            return Err(InvalidEncoding);
+        }
+        self.acc = self.acc << 6 | u32::from(sextet);
+        self.acc_len += 1;
+        if self.acc_len == 4 {
+            for b in &self.acc.to_be_bytes()[1..] {
+                self.out.push(*b);
+            }
+            self.acc = 0;
+            self.acc_len = 0;
+        }
+        Ok(())
+    }
+}
+
+impl<R: ReadCore> ReadCore for Base64Decoder<R>
+where
+    R::Err: From<InvalidEncoding>,
+{
+    type Err = R::Err;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
+        if buf.is_empty() {
+            return Ok(OpRes::Empty);
+        }
+        while self.out.is_empty() {
+            let mut input = [0; CODEC_BUF_SIZE];
+            let n = match self.inner.read(&mut input)? {
+                OpRes::Partial(n) | OpRes::Completly(n) => n.get(),
+                OpRes::Eof if self.acc_len != 0 && self.acc_len + self.padding != 4 => {
+                    return Err(R::Err::from(InvalidEncoding))
+                }
+                res => return Ok(res),
+            };
+            self.out.clear();
+            for c in &input[..n] {
+                self.decode(*c)?;
+            }
+        }
+        Ok(self.out.copy_to(buf))
+    }
+}
@@ -1,8 +1,15 @@
 use core::cmp;
+use core::convert::TryFrom;
 use core::fmt;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::mem::{self, ManuallyDrop, MaybeUninit};
 use core::num::NonZeroUsize;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
 use core::ptr;
 use core::slice;
+use core::task::{ready, Context, Poll, RawWaker, RawWakerVTable, Waker};
 
 #[derive(Copy, Clone, Debug)]
 pub struct UnexpectedEndOfFile;
@@ -16,32 +23,229 @@ pub struct OutOfBounds;
 #[derive(Copy, Clone, Debug)]
 pub struct FormatterError;
 
+/// A non-blocking operation could not make progress.
+#[derive(Copy, Clone, Debug)]
+pub struct WouldBlock;
+
+/// A LEB128 encoded integer was overlong or does not fit into the target type.
+#[derive(Copy, Clone, Debug)]
+pub struct InvalidVarint;
+
+/// The input of a decoder was not validly encoded.
+#[derive(Copy, Clone, Debug)]
+pub struct InvalidEncoding;
+
+/// Error of the in-memory types in this module, which can only fail because
+/// of the conditions signaled by the marker types.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CursorError {
+    UnexpectedEndOfFile,
+    InvalidUtf8,
+    OutOfBounds,
+    FormatterError,
+    InvalidVarint,
+    InvalidEncoding,
+    WouldBlock,
+}
+
+impl From<UnexpectedEndOfFile> for CursorError {
+    fn from(_: UnexpectedEndOfFile) -> Self {
+        Self::UnexpectedEndOfFile
+    }
+}
+
+impl From<InvalidUtf8> for CursorError {
+    fn from(_: InvalidUtf8) -> Self {
+        Self::InvalidUtf8
+    }
+}
+
+impl From<OutOfBounds> for CursorError {
+    fn from(_: OutOfBounds) -> Self {
+        Self::OutOfBounds
+    }
+}
+
+impl From<FormatterError> for CursorError {
+    fn from(_: FormatterError) -> Self {
+        Self::FormatterError
+    }
+}
+
+impl From<InvalidVarint> for CursorError {
+    fn from(_: InvalidVarint) -> Self {
+        Self::InvalidVarint
+    }
+}
+
+impl From<InvalidEncoding> for CursorError {
+    fn from(_: InvalidEncoding) -> Self {
+        Self::InvalidEncoding
+    }
+}
+
+impl From<WouldBlock> for CursorError {
+    fn from(_: WouldBlock) -> Self {
+        Self::WouldBlock
+    }
+}
+
+/// General categories of errors. Mirrors the kinds of `std::io::ErrorKind`
+/// which are meaningful without an operating system.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CoreErrorKind {
+    NotFound,
+    PermissionDenied,
+    ConnectionRefused,
+    ConnectionReset,
+    ConnectionAborted,
+    NotConnected,
+    AddrInUse,
+    AddrNotAvailable,
+    BrokenPipe,
+    AlreadyExists,
+    WouldBlock,
+    InvalidInput,
+    InvalidData,
+    TimedOut,
+    WriteZero,
+    Interrupted,
+    Unsupported,
+    UnexpectedEof,
+    OutOfMemory,
+    Other,
+}
+
+/// Lets generic code inspect an error, e.g. to decide whether an operation
+/// is worth retrying.
+pub trait ClassifyError {
+    fn kind(&self) -> CoreErrorKind;
+
+    /// Whether the operation may succeed when it is tried again later.
+    fn is_transient(&self) -> bool {
+        matches!(
+            self.kind(),
+            CoreErrorKind::WouldBlock | CoreErrorKind::Interrupted | CoreErrorKind::TimedOut
+        )
+    }
+}
+
+impl ClassifyError for UnexpectedEndOfFile {
+    fn kind(&self) -> CoreErrorKind {
+        CoreErrorKind::UnexpectedEof
+    }
+}
+
+impl ClassifyError for InvalidUtf8 {
+    fn kind(&self) -> CoreErrorKind {
+        CoreErrorKind::InvalidData
+    }
+}
+
+impl ClassifyError for OutOfBounds {
+    fn kind(&self) -> CoreErrorKind {
+        CoreErrorKind::InvalidInput
+    }
+}
+
+impl ClassifyError for FormatterError {
+    fn kind(&self) -> CoreErrorKind {
+        CoreErrorKind::Other
+    }
+}
+
+impl ClassifyError for WouldBlock {
+    fn kind(&self) -> CoreErrorKind {
+        CoreErrorKind::WouldBlock
+    }
+}
+
+impl ClassifyError for InvalidVarint {
+    fn kind(&self) -> CoreErrorKind {
+        CoreErrorKind::InvalidData
+    }
+}
+
+impl ClassifyError for InvalidEncoding {
+    fn kind(&self) -> CoreErrorKind {
+        CoreErrorKind::InvalidData
+    }
+}
+
+impl ClassifyError for CursorError {
+    fn kind(&self) -> CoreErrorKind {
+        match self {
+            Self::UnexpectedEndOfFile => UnexpectedEndOfFile.kind(),
+            Self::InvalidUtf8 => InvalidUtf8.kind(),
+            Self::OutOfBounds => OutOfBounds.kind(),
+            Self::FormatterError => FormatterError.kind(),
+            Self::InvalidVarint => InvalidVarint.kind(),
+            Self::InvalidEncoding => InvalidEncoding.kind(),
+            Self::WouldBlock => WouldBlock.kind(),
+        }
+    }
+}
+
 /// Response of a Read or Write Operation
 pub enum OpRes {
-    /// Operation did not complete and should be retried.
+    /// Operation was interrupted and should be retried right away.
     Retry,
+    /// Operation could not make progress without blocking. It should be
+    /// retried once the underlying resource is ready.
+    WouldBlock,
     /// Render was completly read and does not have any more data.
-    /// To signal an zero sized buffer, use `OpRes::Completly`
+    /// To signal an zero sized buffer, use `OpRes::Empty`
     Eof,
+    /// The buffer was empty, so nothing was transferred. This says nothing
+    /// about whether there is data left.
+    Empty,
     /// Buffer was completly filled. There may or may not be data left.
     Completly(NonZeroUsize),
     /// Buffer was partial filled. There may or may not be data left.
     Partial(NonZeroUsize),
 }
 
+/// Result of an operation which transferred `n` of `len` bytes, where no
+/// bytes at all mean the end of file.
+pub(crate) fn written(n: usize, len: usize) -> OpRes {
+    match NonZeroUsize::new(n) {
+        None => OpRes::Eof,
+        Some(n) if n.get() == len => OpRes::Completly(n),
+        Some(n) => OpRes::Partial(n),
+    }
+}
+
 pub trait ReadCore {
-    type Err: From<UnexpectedEndOfFile> + From<InvalidUtf8>;
+    type Err: From<UnexpectedEndOfFile> + From<InvalidUtf8> + From<WouldBlock>;
 
     fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err>;
 
-    unsafe fn initializer(&self) -> Initializer {
-        Initializer::zeroing()
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMutCore<'_>]) -> Result<OpRes, Self::Err> {
+        let buf = bufs
+            .iter_mut()
+            .find(|b| !b.is_empty())
+            .map_or(&mut [][..], |b| &mut **b);
+        self.read(buf)
+    }
+
+    fn is_read_vectored(&self) -> bool {
+        false
+    }
+
+    fn read_buf(&mut self, buf: &mut ReadBufCore<'_>) -> Result<OpRes, Self::Err> {
+        let res = self.read(buf.initialize_unfilled())?;
+        if let OpRes::Partial(n) | OpRes::Completly(n) = res {
+            buf.add_filled(n.get());
+        }
+        Ok(res)
     }
 
     fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<(), Self::Err> {
         while !buf.is_empty() {
-            match self.read(&mut buf)? {
-                OpRes::Eof => return Err(Self::Err::from(UnexpectedEndOfFile)),
+            match self.read(buf)? {
+                OpRes::Eof | OpRes::Empty => return Err(Self::Err::from(UnexpectedEndOfFile)),
+                OpRes::WouldBlock => return Err(Self::Err::from(WouldBlock)),
                 OpRes::Retry => {}
                 OpRes::Partial(n) => buf = &mut buf[n.get()..],
                 OpRes::Completly(_) => break,
@@ -80,21 +284,41 @@ pub trait ReadCore {
     where
         Self: Sized,
     {
-        Take { inner: self, limit }
+        Take::new(self, limit)
+    }
+
+    fn tee<W: WriteCore>(self, writer: W) -> Tee<Self, W>
+    where
+        Self: Sized,
+    {
+        Tee::new(self, writer)
     }
 }
 
 pub trait WriteCore {
-    type Err: From<UnexpectedEndOfFile> + From<FormatterError>;
+    type Err: From<UnexpectedEndOfFile> + From<FormatterError> + From<WouldBlock>;
 
     fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err>;
 
+    fn write_vectored(&mut self, bufs: &[IoSliceCore<'_>]) -> Result<OpRes, Self::Err> {
+        let buf = bufs
+            .iter()
+            .find(|b| !b.is_empty())
+            .map_or(&[][..], |b| &**b);
+        self.write(buf)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        false
+    }
+
     fn flush(&mut self) -> Result<(), Self::Err>;
 
     fn write_all(&mut self, mut buf: &[u8]) -> Result<(), Self::Err> {
         while !buf.is_empty() {
             match self.write(buf)? {
-                OpRes::Eof => return Err(Self::Err::from(UnexpectedEndOfFile)),
+                OpRes::Eof | OpRes::Empty => return Err(Self::Err::from(UnexpectedEndOfFile)),
+                OpRes::WouldBlock => return Err(Self::Err::from(WouldBlock)),
                 OpRes::Retry => {}
                 OpRes::Partial(n) => buf = &buf[n.get()..],
                 OpRes::Completly(_) => break,
@@ -103,6 +327,21 @@ pub trait WriteCore {
         Ok(())
     }
 
+    fn write_all_vectored(&mut self, mut bufs: &mut [IoSliceCore<'_>]) -> Result<(), Self::Err> {
+        IoSliceCore::advance_slices(&mut bufs, 0);
+        while !bufs.is_empty() {
+            match self.write_vectored(bufs)? {
+                OpRes::Eof | OpRes::Empty => return Err(Self::Err::from(UnexpectedEndOfFile)),
+                OpRes::WouldBlock => return Err(Self::Err::from(WouldBlock)),
+                OpRes::Retry => {}
+                OpRes::Partial(n) | OpRes::Completly(n) => {
+                    IoSliceCore::advance_slices(&mut bufs, n.get())
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn write_fmt(&mut self, fmt: fmt::Arguments) -> Result<(), Self::Err> {
         struct Adaptor<'a, T: ?Sized + 'a, E: From<UnexpectedEndOfFile> + From<FormatterError>> {
             inner: &'a mut T,
@@ -130,7 +369,6 @@ pub trait WriteCore {
             inner: self,
             error: Ok(()),
         };
-        let _ = fmt::write(&mut output, fmt);
         match fmt::write(&mut output, fmt) {
             Ok(()) => Ok(()),
             Err(..) => {
@@ -151,195 +389,2681 @@ pub trait WriteCore {
     }
 }
 
-#[derive(Debug)]
-pub struct Bytes<R> {
-    inner: R,
-}
+/// Number which can be converted from and to its byte representation.
+pub trait Primitive: Copy {
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Default;
 
-impl<R> Bytes<R> {
-    pub fn new(inner: R) -> Self {
-        Self { inner }
-    }
+    fn from_le_bytes(bytes: Self::Bytes) -> Self;
+
+    fn from_be_bytes(bytes: Self::Bytes) -> Self;
+
+    fn to_le_bytes(self) -> Self::Bytes;
+
+    fn to_be_bytes(self) -> Self::Bytes;
 }
 
-impl<E, R: ReadCore<Err = E>> Iterator for Bytes<R>
-where
-    E: From<InvalidUtf8> + From<UnexpectedEndOfFile>,
-{
-    type Item = Result<u8, E>;
+macro_rules! impl_primitive {
+    ($($ty:ty),*) => {
+        $(
+            impl Primitive for $ty {
+                type Bytes = [u8; mem::size_of::<$ty>()];
 
-    fn next(&mut self) -> Option<Result<u8, E>> {
-        let mut byte = 0;
-        loop {
-            match self.inner.read(slice::from_mut(&mut byte)) {
-                Ok(OpRes::Eof) => return None,
-                Ok(OpRes::Retry) => {}
-                Ok(OpRes::Partial(_)) | Ok(OpRes::Completly(_)) => return Some(Ok(byte)),
-                Err(err) => return Some(Err(err)),
+                fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                    <$ty>::from_le_bytes(bytes)
+                }
+
+                fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                    <$ty>::from_be_bytes(bytes)
+                }
+
+                fn to_le_bytes(self) -> Self::Bytes {
+                    <$ty>::to_le_bytes(self)
+                }
+
+                fn to_be_bytes(self) -> Self::Bytes {
+                    <$ty>::to_be_bytes(self)
+                }
             }
-        }
-    }
+        )*
+    };
 }
 
-pub struct Chain<T, U> {
-    first: T,
-    second: U,
-    done_first: bool,
+impl_primitive!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+/// Byte order used to encode a `Primitive`.
+pub trait Endian {
+    fn from_bytes<T: Primitive>(bytes: T::Bytes) -> T;
+
+    fn to_bytes<T: Primitive>(value: T) -> T::Bytes;
 }
 
-impl<T, U> Chain<T, U> {
-    pub fn new(first: T, second: U) -> Self {
-        Self {
-            first,
-            second,
-            done_first: false,
-        }
+#[derive(Copy, Clone, Debug)]
+pub enum LittleEndian {}
+
+#[derive(Copy, Clone, Debug)]
+pub enum BigEndian {}
+
+pub type NetworkEndian = BigEndian;
+
+#[cfg(target_endian = "little")]
+pub type NativeEndian = LittleEndian;
+
+#[cfg(target_endian = "big")]
+pub type NativeEndian = BigEndian;
+
+impl Endian for LittleEndian {
+    fn from_bytes<T: Primitive>(bytes: T::Bytes) -> T {
+        T::from_le_bytes(bytes)
     }
-}
 
-impl<T, U> Chain<T, U> {
-    pub fn into_inner(self) -> (T, U) {
-        (self.first, self.second)
+    fn to_bytes<T: Primitive>(value: T) -> T::Bytes {
+        value.to_le_bytes()
     }
+}
 
-    pub fn get_ref(&self) -> (&T, &U) {
-        (&self.first, &self.second)
+impl Endian for BigEndian {
+    fn from_bytes<T: Primitive>(bytes: T::Bytes) -> T {
+        T::from_be_bytes(bytes)
     }
 
-    pub fn get_mut(&mut self) -> (&mut T, &mut U) {
-        (&mut self.first, &mut self.second)
+    fn to_bytes<T: Primitive>(value: T) -> T::Bytes {
+        value.to_be_bytes()
     }
 }
 
-impl<T: fmt::Debug, U: fmt::Debug> fmt::Debug for Chain<T, U> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Chain")
-            .field("t", &self.first)
-            .field("u", &self.second)
-            .finish()
-    }
+/// Unsigned integer which can be encoded as unsigned LEB128.
+pub trait Unsigned: Copy {
+    const BITS: u32;
+
+    fn from_u128(value: u128) -> Self;
+
+    fn into_u128(self) -> u128;
 }
 
-impl<T: ReadCore<Err = E>, U: ReadCore<Err = E>, E> ReadCore for Chain<T, U>
-where
-    E: From<InvalidUtf8> + From<UnexpectedEndOfFile>,
-{
-    type Err = E;
+/// Signed integer which can be encoded as signed LEB128.
+pub trait Signed: Copy {
+    const BITS: u32;
+    const MIN: i128;
+    const MAX: i128;
 
-    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, E> {
-        if !self.done_first {
-            match self.first.read(buf)? {
-                OpRes::Eof => self.done_first = true,
-                op => return Ok(op),
+    fn from_i128(value: i128) -> Self;
+
+    fn into_i128(self) -> i128;
+}
+
+macro_rules! impl_varint {
+    ($trait:ident, $wide:ty, $from:ident, $into:ident; $($ty:ty),*) => {
+        $(
+            impl $trait for $ty {
+                const BITS: u32 = <$ty>::BITS;
+
+                fn $from(value: $wide) -> Self {
+                    value as $ty
+                }
+
+                fn $into(self) -> $wide {
+                    self as $wide
+                }
             }
-        }
-        self.second.read(buf)
-    }
+        )*
+    };
+}
 
-    // fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
-    //     if !self.done_first {
-    //         match self.first.read_vectored(bufs)? {
-    //             0 if bufs.iter().any(|b| !b.is_empty()) => self.done_first = true,
-    //             n => return Ok(n),
-    //         }
-    //     }
-    //     self.second.read_vectored(bufs)
-    // }
-
-    unsafe fn initializer(&self) -> Initializer {
-        let initializer = self.first.initializer();
-        if initializer.should_initialize() {
-            initializer
-        } else {
-            self.second.initializer()
-        }
-    }
+impl_varint!(Unsigned, u128, from_u128, into_u128; u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_signed {
+    ($($ty:ty),*) => {
+        $(
+            impl Signed for $ty {
+                const BITS: u32 = <$ty>::BITS;
+                const MIN: i128 = <$ty>::MIN as i128;
+                const MAX: i128 = <$ty>::MAX as i128;
+
+                fn from_i128(value: i128) -> Self {
+                    value as $ty
+                }
+
+                fn into_i128(self) -> i128 {
+                    self as i128
+                }
+            }
+        )*
+    };
 }
 
-pub struct Take<T> {
-    inner: T,
-    limit: u64,
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+/// Maximum length of a LEB128 encoded 128 bit integer.
+const MAX_LEB128_LEN: usize = 19;
+
+macro_rules! read_primitive {
+    ($($le:ident, $be:ident => $ty:ty;)*) => {
+        $(
+            fn $le(&mut self) -> Result<$ty, Self::Err> {
+                self.read_int::<$ty, LittleEndian>()
+            }
+
+            fn $be(&mut self) -> Result<$ty, Self::Err> {
+                self.read_int::<$ty, BigEndian>()
+            }
+        )*
+    };
 }
 
-impl<T> Take<T> {
-    pub fn new(inner: T, limit: u64) -> Self {
-        Self { inner, limit }
+/// Typed reads on top of `ReadCore::read_exact`. Running out of data before
+/// the value is complete results in `UnexpectedEndOfFile`.
+pub trait ReadCoreExt: ReadCore {
+    fn read_int<T: Primitive, E: Endian>(&mut self) -> Result<T, Self::Err> {
+        let mut bytes = T::Bytes::default();
+        self.read_exact(bytes.as_mut())?;
+        Ok(E::from_bytes(bytes))
     }
 
-    pub fn limit(&self) -> u64 {
-        self.limit
+    fn read_u8(&mut self) -> Result<u8, Self::Err> {
+        self.read_int::<u8, LittleEndian>()
     }
 
-    pub fn set_limit(&mut self, limit: u64) {
-        self.limit = limit;
+    fn read_i8(&mut self) -> Result<i8, Self::Err> {
+        self.read_int::<i8, LittleEndian>()
     }
 
-    pub fn into_inner(self) -> T {
-        self.inner
+    /// Reads an unsigned LEB128 encoded integer. Encodings which are longer
+    /// than necessary or do not fit into `T` are rejected with `InvalidVarint`.
+    fn read_uleb128<T: Unsigned>(&mut self) -> Result<T, Self::Err>
+    where
+        Self::Err: From<InvalidVarint>,
+    {
+        let mut result = 0u128;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            let payload = u128::from(byte & 0x7f);
+            if shift >= T::BITS || payload.checked_shr(T::BITS - shift).unwrap_or(0) != 0 {
+                return Err(Self::Err::from(InvalidVarint));
+            }
+            result |= payload << shift;
+            if byte & 0x80 == 0 {
+                if byte == 0 && shift > 0 {
+                    return Err(Self::Err::from(InvalidVarint));
+                }
+                return Ok(T::from_u128(result));
+            }
+            shift += 7;
+        }
     }
 
-    pub fn get_ref(&self) -> &T {
-        &self.inner
+    /// Reads a signed LEB128 encoded integer. Encodings which are longer than
+    /// necessary or do not fit into `T` are rejected with `InvalidVarint`.
+    fn read_sleb128<T: Signed>(&mut self) -> Result<T, Self::Err>
+    where
+        Self::Err: From<InvalidVarint>,
+    {
+        let mut result = 0i128;
+        let mut shift = 0;
+        let mut prev = 0u8;
+        loop {
+            let byte = self.read_u8()?;
+            let payload = byte & 0x7f;
+            if shift >= 128 {
+                return Err(Self::Err::from(InvalidVarint));
+            }
+            if shift + 7 > 128 {
+                // Bits which do not fit into an i128 have to repeat the sign bit
+                let dropped = (((payload << 1) as i8) >> 1) >> (128 - shift - 1);
+                if dropped != 0 && dropped != -1 {
+                    return Err(Self::Err::from(InvalidVarint));
+                }
+            }
+            result |= i128::from(payload) << shift;
+            shift += 7;
+
+            if byte & 0x80 == 0 {
+                let negative = byte & 0x40 != 0;
+                let redundant =
+                    (byte == 0 && prev & 0x40 == 0) || (byte == 0x7f && prev & 0x40 != 0);
+                if shift > 7 && redundant {
+                    return Err(Self::Err::from(InvalidVarint));
+                }
+                if negative && shift < 128 {
+                    result |= -1i128 << shift;
+                }
+                if result < T::MIN || result > T::MAX {
+                    return Err(Self::Err::from(InvalidVarint));
+                }
+                return Ok(T::from_i128(result));
+            }
+            prev = byte;
+        }
     }
 
-    pub fn get_mut(&mut self) -> &mut T {
-        &mut self.inner
+    read_primitive! {
+        read_u16_le, read_u16_be => u16;
+        read_u32_le, read_u32_be => u32;
+        read_u64_le, read_u64_be => u64;
+        read_u128_le, read_u128_be => u128;
+        read_i16_le, read_i16_be => i16;
+        read_i32_le, read_i32_be => i32;
+        read_i64_le, read_i64_be => i64;
+        read_i128_le, read_i128_be => i128;
+        read_f32_le, read_f32_be => f32;
+        read_f64_le, read_f64_be => f64;
     }
 }
 
-impl<E, T: ReadCore<Err = E>> ReadCore for Take<T>
-where
-    E: From<InvalidUtf8> + From<UnexpectedEndOfFile>,
-{
-    type Err = E;
+impl<R: ReadCore + ?Sized> ReadCoreExt for R {}
 
-    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
-        if self.limit == 0 {
-            return Ok(OpRes::Eof);
-        }
+macro_rules! write_primitive {
+    ($($le:ident, $be:ident => $ty:ty;)*) => {
+        $(
+            fn $le(&mut self, value: $ty) -> Result<(), Self::Err> {
+                self.write_int::<$ty, LittleEndian>(value)
+            }
 
-        let max = cmp::min(buf.len() as u64, self.limit) as usize;
-        match self.inner.read(&mut buf[..max])? {
-            OpRes::Partial(n) => {
-                self.limit -= n.get() as u64;
-                Ok(OpRes::Partial(n))
+            fn $be(&mut self, value: $ty) -> Result<(), Self::Err> {
+                self.write_int::<$ty, BigEndian>(value)
+            }
+        )*
+    };
+}
+
+/// Typed writes on top of `WriteCore::write_all`. A writer which is unable to
+/// take the whole value results in `UnexpectedEndOfFile`.
+pub trait WriteCoreExt: WriteCore {
+    fn write_int<T: Primitive, E: Endian>(&mut self, value: T) -> Result<(), Self::Err> {
+        self.write_all(E::to_bytes(value).as_ref())
+    }
+
+    fn write_u8(&mut self, value: u8) -> Result<(), Self::Err> {
+        self.write_int::<u8, LittleEndian>(value)
+    }
+
+    fn write_i8(&mut self, value: i8) -> Result<(), Self::Err> {
+        self.write_int::<i8, LittleEndian>(value)
+    }
+
+    fn write_uleb128<T: Unsigned>(&mut self, value: T) -> Result<(), Self::Err> {
+        let mut value = value.into_u128();
+        let mut buf = [0; MAX_LEB128_LEN];
+        let mut len = 0;
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                buf[len] = byte;
+                len += 1;
+                break;
             }
-            res @ OpRes::Completly(_) => {
-                self.limit -= max as u64;
-                Ok(res)
+            buf[len] = byte | 0x80;
+            len += 1;
+        }
+        self.write_all(&buf[..len])
+    }
+
+    fn write_sleb128<T: Signed>(&mut self, value: T) -> Result<(), Self::Err> {
+        let mut value = value.into_i128();
+        let mut buf = [0; MAX_LEB128_LEN];
+        let mut len = 0;
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0) {
+                buf[len] = byte;
+                len += 1;
+                break;
             }
-            op => Ok(op),
+            buf[len] = byte | 0x80;
+            len += 1;
         }
+        self.write_all(&buf[..len])
     }
 
-    unsafe fn initializer(&self) -> Initializer {
-        self.inner.initializer()
+    write_primitive! {
+        write_u16_le, write_u16_be => u16;
+        write_u32_le, write_u32_be => u32;
+        write_u64_le, write_u64_be => u64;
+        write_u128_le, write_u128_be => u128;
+        write_i16_le, write_i16_be => i16;
+        write_i32_le, write_i32_be => i32;
+        write_i64_le, write_i64_be => i64;
+        write_i128_le, write_i128_be => i128;
+        write_f32_le, write_f32_be => f32;
+        write_f64_le, write_f64_be => f64;
     }
 }
 
-#[derive(Debug)]
-pub struct Initializer(bool);
+impl<W: WriteCore + ?Sized> WriteCoreExt for W {}
 
-impl Initializer {
-    #[inline]
-    pub fn zeroing() -> Initializer {
-        Initializer(true)
-    }
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+pub enum SeekFrom {
+    Start(u64),
+    End(i64),
+    Current(i64),
+}
 
-    #[inline]
-    pub unsafe fn nop() -> Initializer {
-        Initializer(false)
-    }
+pub trait SeekCore {
+    type Err: From<OutOfBounds>;
 
-    #[inline]
-    pub fn should_initialize(&self) -> bool {
-        self.0
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Err>;
+
+    fn rewind(&mut self) -> Result<(), Self::Err> {
+        self.seek(SeekFrom::Start(0))?;
+        Ok(())
     }
 
-    #[inline]
-    pub fn initialize(&self, buf: &mut [u8]) {
-        if self.should_initialize() {
-            unsafe { ptr::write_bytes(buf.as_mut_ptr(), 0, buf.len()) }
+    fn stream_len(&mut self) -> Result<u64, Self::Err> {
+        let old_pos = self.stream_position()?;
+        let len = self.seek(SeekFrom::End(0))?;
+        if old_pos != len {
+            self.seek(SeekFrom::Start(old_pos))?;
+        }
+        Ok(len)
+    }
+
+    fn stream_position(&mut self) -> Result<u64, Self::Err> {
+        self.seek(SeekFrom::Current(0))
+    }
+}
+
+fn seek_offset(base: u64, offset: i64) -> Result<u64, OutOfBounds> {
+    base.checked_add_signed(offset).ok_or(OutOfBounds)
+}
+
+/// Response of a `BufReadCore::fill_buf` Operation
+pub enum FillRes<'a> {
+    /// Operation was interrupted and should be retried right away.
+    Retry,
+    /// Operation could not make progress without blocking. It should be
+    /// retried once the underlying resource is ready.
+    WouldBlock,
+    /// Reader was completly read and does not have any more data.
+    Eof,
+    /// Buffered data which was not consumed yet. Never empty.
+    Data(&'a [u8]),
+}
+
+pub trait BufReadCore: ReadCore {
+    fn fill_buf(&mut self) -> Result<FillRes<'_>, Self::Err>;
+
+    fn consume(&mut self, amt: usize);
+}
+
+/// Asynchronous version of `ReadCore`. Instead of returning `OpRes::Retry` or
+/// `OpRes::WouldBlock`, implementations return `Poll::Pending` and wake the
+/// task once data is available.
+pub trait AsyncReadCore {
+    type Err: From<UnexpectedEndOfFile> + From<InvalidUtf8> + From<WouldBlock>;
+
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<OpRes, Self::Err>>;
+
+    fn read_exact<'a>(&'a mut self, buf: &'a mut [u8]) -> ReadExact<'a, Self>
+    where
+        Self: Unpin,
+    {
+        ReadExact { reader: self, buf }
+    }
+
+    fn chain<R: AsyncReadCore>(self, next: R) -> Chain<Self, R>
+    where
+        Self: Sized,
+    {
+        Chain::new(self, next)
+    }
+
+    fn take(self, limit: u64) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take::new(self, limit)
+    }
+}
+
+/// Asynchronous version of `WriteCore`. Instead of returning `OpRes::Retry` or
+/// `OpRes::WouldBlock`, implementations return `Poll::Pending` and wake the
+/// task once the writer is able to accept data again.
+pub trait AsyncWriteCore {
+    type Err: From<UnexpectedEndOfFile> + From<FormatterError> + From<WouldBlock>;
+
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<OpRes, Self::Err>>;
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Err>>;
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Err>>;
+
+    fn write_all<'a>(&'a mut self, buf: &'a [u8]) -> WriteAll<'a, Self>
+    where
+        Self: Unpin,
+    {
+        WriteAll { writer: self, buf }
+    }
+
+    fn flush(&mut self) -> Flush<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Flush { writer: self }
+    }
+
+    fn close(&mut self) -> Close<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Close { writer: self }
+    }
+}
+
+/// Turns an `OpRes::Retry` returned by an asynchronous operation into
+/// `Poll::Pending`, scheduling the task to be polled again right away.
+/// Without a reactor to register with, `OpRes::WouldBlock` is handled the
+/// same way.
+fn retry_to_pending<E>(
+    res: Poll<Result<OpRes, E>>,
+    cx: &mut Context<'_>,
+) -> Poll<Result<OpRes, E>> {
+    match res {
+        Poll::Ready(Ok(OpRes::Retry)) | Poll::Ready(Ok(OpRes::WouldBlock)) => {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+        res => res,
+    }
+}
+
+/// Raw representation of a slice. On unix and windows the layout matches the
+/// one used by the vectored I/O functions of the platform, which allows the
+/// std layer to convert between slices of `IoSlice` and `IoSliceCore` without
+/// copying.
+#[cfg(not(windows))]
+#[derive(Copy, Clone)]
+#[repr(C)]
+struct RawSlice {
+    ptr: *mut u8,
+    len: usize,
+}
+
+#[cfg(windows)]
+#[derive(Copy, Clone)]
+#[repr(C)]
+struct RawSlice {
+    len: u32,
+    ptr: *mut u8,
+}
+
+impl RawSlice {
+    #[cfg(not(windows))]
+    fn new(ptr: *mut u8, len: usize) -> Self {
+        Self { ptr, len }
+    }
+
+    #[cfg(windows)]
+    fn new(ptr: *mut u8, len: usize) -> Self {
+        assert!(len <= u32::MAX as usize);
+        Self {
+            len: len as u32,
+            ptr,
+        }
+    }
+
+    #[cfg(not(windows))]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[cfg(windows)]
+    fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    fn advance(&mut self, n: usize) {
+        assert!(n <= self.len(), "advancing IoSlice beyond its length");
+        *self = Self::new(unsafe { self.ptr.add(n) }, self.len() - n);
+    }
+}
+
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct IoSliceCore<'a> {
+    raw: RawSlice,
+    _p: PhantomData<&'a [u8]>,
+}
+
+unsafe impl Send for IoSliceCore<'_> {}
+
+unsafe impl Sync for IoSliceCore<'_> {}
+
+impl<'a> IoSliceCore<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self {
+            raw: RawSlice::new(buf.as_ptr() as *mut u8, buf.len()),
+            _p: PhantomData,
+        }
+    }
+
+    /// Returns the underlying slice with its original lifetime.
+    pub fn as_slice(self) -> &'a [u8] {
+        unsafe { slice::from_raw_parts(self.raw.ptr, self.raw.len()) }
+    }
+
+    pub fn advance(&mut self, n: usize) {
+        self.raw.advance(n)
+    }
+
+    pub fn advance_slices(bufs: &mut &mut [IoSliceCore<'a>], n: usize) {
+        let mut remove = 0;
+        let mut left = n;
+        for buf in bufs.iter() {
+            match left.checked_sub(buf.len()) {
+                Some(rem) => {
+                    left = rem;
+                    remove += 1;
+                }
+                None => break,
+            }
+        }
+
+        *bufs = &mut mem::take(bufs)[remove..];
+        if bufs.is_empty() {
+            assert!(left == 0, "advancing io slices beyond their length");
+        } else {
+            bufs[0].advance(left);
+        }
+    }
+}
+
+impl Deref for IoSliceCore<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.raw.ptr, self.raw.len()) }
+    }
+}
+
+impl fmt::Debug for IoSliceCore<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[repr(transparent)]
+pub struct IoSliceMutCore<'a> {
+    raw: RawSlice,
+    _p: PhantomData<&'a mut [u8]>,
+}
+
+unsafe impl Send for IoSliceMutCore<'_> {}
+
+unsafe impl Sync for IoSliceMutCore<'_> {}
+
+impl<'a> IoSliceMutCore<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self {
+            raw: RawSlice::new(buf.as_mut_ptr(), buf.len()),
+            _p: PhantomData,
+        }
+    }
+
+    /// Returns the underlying slice with its original lifetime.
+    pub fn into_slice(self) -> &'a mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.raw.ptr, self.raw.len()) }
+    }
+
+    pub fn advance(&mut self, n: usize) {
+        self.raw.advance(n)
+    }
+
+    pub fn advance_slices(bufs: &mut &mut [IoSliceMutCore<'a>], n: usize) {
+        let mut remove = 0;
+        let mut left = n;
+        for buf in bufs.iter() {
+            match left.checked_sub(buf.len()) {
+                Some(rem) => {
+                    left = rem;
+                    remove += 1;
+                }
+                None => break,
+            }
+        }
+
+        *bufs = &mut mem::take(bufs)[remove..];
+        if bufs.is_empty() {
+            assert!(left == 0, "advancing io slices beyond their length");
+        } else {
+            bufs[0].advance(left);
+        }
+    }
+}
+
+impl Deref for IoSliceMutCore<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.raw.ptr, self.raw.len()) }
+    }
+}
+
+impl DerefMut for IoSliceMutCore<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.raw.ptr, self.raw.len()) }
+    }
+}
+
+impl fmt::Debug for IoSliceMutCore<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[derive(Debug)]
+pub struct Bytes<R> {
+    inner: R,
+}
+
+impl<R> Bytes<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+}
+
+impl<E, R: ReadCore<Err = E>> Iterator for Bytes<R>
+where
+    E: From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock>,
+{
+    type Item = Result<u8, E>;
+
+    fn next(&mut self) -> Option<Result<u8, E>> {
+        let mut byte = 0;
+        loop {
+            match self.inner.read(slice::from_mut(&mut byte)) {
+                Ok(OpRes::Eof) | Ok(OpRes::Empty) => return None,
+                Ok(OpRes::Retry) => {}
+                Ok(OpRes::WouldBlock) => return Some(Err(E::from(WouldBlock))),
+                Ok(OpRes::Partial(_)) | Ok(OpRes::Completly(_)) => return Some(Ok(byte)),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+pub struct Chain<T, U> {
+    first: T,
+    second: U,
+    done_first: bool,
+}
+
+impl<T, U> Chain<T, U> {
+    pub fn new(first: T, second: U) -> Self {
+        Self {
+            first,
+            second,
+            done_first: false,
+        }
+    }
+}
+
+impl<T, U> Chain<T, U> {
+    pub fn into_inner(self) -> (T, U) {
+        (self.first, self.second)
+    }
+
+    pub fn get_ref(&self) -> (&T, &U) {
+        (&self.first, &self.second)
+    }
+
+    pub fn get_mut(&mut self) -> (&mut T, &mut U) {
+        (&mut self.first, &mut self.second)
+    }
+}
+
+impl<T: fmt::Debug, U: fmt::Debug> fmt::Debug for Chain<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Chain")
+            .field("t", &self.first)
+            .field("u", &self.second)
+            .finish()
+    }
+}
+
+impl<T: ReadCore<Err = E>, U: ReadCore<Err = E>, E> ReadCore for Chain<T, U>
+where
+    E: From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock>,
+{
+    type Err = E;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, E> {
+        if !self.done_first {
+            match self.first.read(buf)? {
+                OpRes::Eof => self.done_first = true,
+                op => return Ok(op),
+            }
+        }
+        self.second.read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMutCore<'_>]) -> Result<OpRes, E> {
+        if !self.done_first {
+            match self.first.read_vectored(bufs)? {
+                OpRes::Eof => self.done_first = true,
+                op => return Ok(op),
+            }
+        }
+        self.second.read_vectored(bufs)
+    }
+}
+
+/// Positions are relative to the start of `first`, followed by the start of
+/// `second`. Seeking moves both readers so that subsequent reads continue at
+/// the requested offset of the combined stream.
+impl<T: SeekCore<Err = E>, U: SeekCore<Err = E>, E> SeekCore for Chain<T, U>
+where
+    E: From<OutOfBounds>,
+{
+    type Err = E;
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, E> {
+        let first_len = self.first.stream_len()?;
+        let new_pos = match pos {
+            SeekFrom::Start(n) => n,
+            SeekFrom::End(n) => {
+                let len = first_len
+                    .checked_add(self.second.stream_len()?)
+                    .ok_or(OutOfBounds)?;
+                seek_offset(len, n)?
+            }
+            SeekFrom::Current(n) => seek_offset(self.stream_position()?, n)?,
+        };
+
+        if new_pos < first_len {
+            self.first.seek(SeekFrom::Start(new_pos))?;
+            self.second.rewind()?;
+            self.done_first = false;
+        } else {
+            self.first.seek(SeekFrom::End(0))?;
+            self.second.seek(SeekFrom::Start(new_pos - first_len))?;
+            self.done_first = true;
+        }
+        Ok(new_pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64, E> {
+        if self.done_first {
+            let first_len = self.first.stream_len()?;
+            let second_pos = self.second.stream_position()?;
+            Ok(first_len.checked_add(second_pos).ok_or(OutOfBounds)?)
+        } else {
+            self.first.stream_position()
+        }
+    }
+}
+
+impl<T: AsyncReadCore<Err = E> + Unpin, U: AsyncReadCore<Err = E> + Unpin, E> AsyncReadCore
+    for Chain<T, U>
+where
+    E: From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock>,
+{
+    type Err = E;
+
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<OpRes, E>> {
+        let this = &mut *self;
+        if !this.done_first {
+            match ready!(Pin::new(&mut this.first).poll_read(cx, buf))? {
+                OpRes::Eof => this.done_first = true,
+                op => return retry_to_pending(Poll::Ready(Ok(op)), cx),
+            }
+        }
+        retry_to_pending(Pin::new(&mut this.second).poll_read(cx, buf), cx)
+    }
+}
+
+pub struct Take<T> {
+    inner: T,
+    // Position relative to the point at which the `Take` was created
+    pos: u64,
+    limit: u64,
+}
+
+impl<T> Take<T> {
+    pub fn new(inner: T, limit: u64) -> Self {
+        Self {
+            inner,
+            pos: 0,
+            limit,
+        }
+    }
+
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Sets the number of bytes which can still be read. The origin used for
+    /// seeking stays the same.
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = limit;
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<E, T: ReadCore<Err = E>> ReadCore for Take<T>
+where
+    E: From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock>,
+{
+    type Err = E;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
+        if self.limit == 0 {
+            return Ok(OpRes::Eof);
+        }
+
+        let max = cmp::min(buf.len() as u64, self.limit) as usize;
+        match self.inner.read(&mut buf[..max])? {
+            OpRes::Partial(n) => {
+                self.limit -= n.get() as u64;
+                self.pos += n.get() as u64;
+                Ok(OpRes::Partial(n))
+            }
+            OpRes::Completly(n) => {
+                self.limit -= n.get() as u64;
+                self.pos += n.get() as u64;
+                // The buffer was truncated to the limit, so it is only filled partially
+                if max < buf.len() {
+                    Ok(OpRes::Partial(n))
+                } else {
+                    Ok(OpRes::Completly(n))
+                }
+            }
+            op => Ok(op),
+        }
+    }
+}
+
+impl<E, T: AsyncReadCore<Err = E> + Unpin> AsyncReadCore for Take<T>
+where
+    E: From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock>,
+{
+    type Err = E;
+
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<OpRes, E>> {
+        if self.limit == 0 {
+            return Poll::Ready(Ok(OpRes::Eof));
+        }
+
+        let max = cmp::min(buf.len() as u64, self.limit) as usize;
+        let res = Pin::new(&mut self.inner).poll_read(cx, &mut buf[..max]);
+        match retry_to_pending(res, cx)? {
+            Poll::Ready(OpRes::Partial(n)) => {
+                self.limit -= n.get() as u64;
+                self.pos += n.get() as u64;
+                Poll::Ready(Ok(OpRes::Partial(n)))
+            }
+            Poll::Ready(OpRes::Completly(n)) => {
+                self.limit -= n.get() as u64;
+                self.pos += n.get() as u64;
+                if max < buf.len() {
+                    Poll::Ready(Ok(OpRes::Partial(n)))
+                } else {
+                    Poll::Ready(Ok(OpRes::Completly(n)))
+                }
+            }
+            res => res.map(Ok),
+        }
+    }
+}
+
+/// Positions are relative to the point at which the `Take` was created. The
+/// window ends after the bytes which can still be read, seeking outside of it
+/// fails with `OutOfBounds`.
+impl<E, T: SeekCore<Err = E>> SeekCore for Take<T>
+where
+    E: From<OutOfBounds>,
+{
+    type Err = E;
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, E> {
+        let old_pos = self.pos;
+        let end = self.pos.saturating_add(self.limit);
+        let new_pos = match pos {
+            SeekFrom::Start(n) => n,
+            SeekFrom::End(n) => seek_offset(end, n)?,
+            SeekFrom::Current(n) => seek_offset(old_pos, n)?,
+        };
+        if new_pos > end {
+            return Err(E::from(OutOfBounds));
+        }
+
+        let offset = if new_pos >= old_pos {
+            i64::try_from(new_pos - old_pos).map_err(|_| OutOfBounds)?
+        } else {
+            -i64::try_from(old_pos - new_pos).map_err(|_| OutOfBounds)?
+        };
+        self.inner.seek(SeekFrom::Current(offset))?;
+        self.limit = end - new_pos;
+        self.pos = new_pos;
+        Ok(new_pos)
+    }
+
+    /// The window is cut short if the inner stream ends before it.
+    fn stream_len(&mut self) -> Result<u64, E> {
+        let origin = self.inner.stream_position()?.saturating_sub(self.pos);
+        let inner_len = self.inner.stream_len()?.saturating_sub(origin);
+        Ok(cmp::min(self.pos.saturating_add(self.limit), inner_len))
+    }
+
+    fn stream_position(&mut self) -> Result<u64, E> {
+        Ok(self.pos)
+    }
+}
+
+/// Action taken by a `Tee` when mirroring the read data into its writer fails.
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+pub enum TeePolicy {
+    /// Hand out the data of the failing `read` and return the error from the
+    /// next `read`, before reading any further. The writer may have received
+    /// a part of that data.
+    Propagate,
+    /// Drop the error and keep on reading and mirroring.
+    Ignore,
+    /// Keep the first error for `take_error` and stop mirroring until it was
+    /// taken. Reading continues.
+    Stash,
+}
+
+/// Error of a `Tee`, which tells whether the reader or the writer failed.
+/// Errors created through the marker types are read errors.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TeeError<R, W> {
+    Read(R),
+    Write(W),
+}
+
+impl<R: From<UnexpectedEndOfFile>, W> From<UnexpectedEndOfFile> for TeeError<R, W> {
+    fn from(err: UnexpectedEndOfFile) -> Self {
+        Self::Read(R::from(err))
+    }
+}
+
+impl<R: From<InvalidUtf8>, W> From<InvalidUtf8> for TeeError<R, W> {
+    fn from(err: InvalidUtf8) -> Self {
+        Self::Read(R::from(err))
+    }
+}
+
+impl<R: From<WouldBlock>, W> From<WouldBlock> for TeeError<R, W> {
+    fn from(err: WouldBlock) -> Self {
+        Self::Read(R::from(err))
+    }
+}
+
+impl<R: ClassifyError, W: ClassifyError> ClassifyError for TeeError<R, W> {
+    fn kind(&self) -> CoreErrorKind {
+        match self {
+            Self::Read(err) => err.kind(),
+            Self::Write(err) => err.kind(),
+        }
+    }
+}
+
+/// Reader which writes all data read from `inner` into `writer`.
+pub struct Tee<R, W: WriteCore> {
+    inner: R,
+    writer: W,
+    policy: TeePolicy,
+    error: Option<W::Err>,
+}
+
+impl<R, W: WriteCore> Tee<R, W> {
+    pub fn new(inner: R, writer: W) -> Self {
+        Self {
+            inner,
+            writer,
+            policy: TeePolicy::Propagate,
+            error: None,
+        }
+    }
+
+    pub fn policy(&self) -> TeePolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: TeePolicy) {
+        self.policy = policy;
+    }
+
+    /// Returns the error stashed with `TeePolicy::Stash`, or the error of
+    /// `TeePolicy::Propagate` which was not returned yet, and resumes
+    /// mirroring.
+    pub fn take_error(&mut self) -> Option<W::Err> {
+        self.error.take()
+    }
+
+    pub fn into_inner(self) -> (R, W) {
+        (self.inner, self.writer)
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    pub fn writer_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+}
+
+impl<R: ReadCore, W: WriteCore> ReadCore for Tee<R, W> {
+    type Err = TeeError<R::Err, W::Err>;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
+        if self.policy == TeePolicy::Propagate {
+            if let Some(err) = self.error.take() {
+                return Err(TeeError::Write(err));
+            }
+        }
+        let res = self.inner.read(buf).map_err(TeeError::Read)?;
+        let n = match res {
+            OpRes::Partial(n) | OpRes::Completly(n) => n.get(),
+            _ => return Ok(res),
+        };
+        if self.error.is_none() {
+            if let Err(err) = self.writer.write_all(&buf[..n]) {
+                match self.policy {
+                    TeePolicy::Ignore => {}
+                    TeePolicy::Propagate | TeePolicy::Stash => self.error = Some(err),
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Action taken by `Broadcast` and `BroadcastArray` when a sink fails.
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+pub enum BroadcastPolicy {
+    /// Return the error of the first failing sink. Sinks before it may
+    /// already have received data, sinks after it did not. If every sink
+    /// consumed a common part of the buffer, `write` reports that part as
+    /// `OpRes::Partial` and returns the error from the next call.
+    Propagate,
+    /// Stop writing to a failed sink and keep its error. Once no sink is
+    /// attached anymore, the kept errors are returned and every further
+    /// `write` reports `OpRes::Eof`, until the sinks are attached again by
+    /// taking their errors.
+    Detach,
+}
+
+/// Error of a `Broadcast`. Errors created through the marker types are
+/// reported for both sinks.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BroadcastError<A, B> {
+    First(A),
+    Second(B),
+    Both(A, B),
+    /// The buffer is shorter than the data a sink already consumed ahead of
+    /// what `write` reported as written.
+    Diverged,
+}
+
+impl<A, B> From<UnexpectedEndOfFile> for BroadcastError<A, B>
+where
+    A: From<UnexpectedEndOfFile>,
+    B: From<UnexpectedEndOfFile>,
+{
+    fn from(err: UnexpectedEndOfFile) -> Self {
+        Self::Both(A::from(err), B::from(err))
+    }
+}
+
+impl<A, B> From<FormatterError> for BroadcastError<A, B>
+where
+    A: From<FormatterError>,
+    B: From<FormatterError>,
+{
+    fn from(err: FormatterError) -> Self {
+        Self::Both(A::from(err), B::from(err))
+    }
+}
+
+impl<A, B> From<WouldBlock> for BroadcastError<A, B>
+where
+    A: From<WouldBlock>,
+    B: From<WouldBlock>,
+{
+    fn from(err: WouldBlock) -> Self {
+        Self::Both(A::from(err), B::from(err))
+    }
+}
+
+impl<A: ClassifyError, B: ClassifyError> ClassifyError for BroadcastError<A, B> {
+    fn kind(&self) -> CoreErrorKind {
+        match self {
+            Self::First(err) | Self::Both(err, _) => err.kind(),
+            Self::Second(err) => err.kind(),
+            Self::Diverged => CoreErrorKind::InvalidInput,
+        }
+    }
+}
+
+/// State of a sink of `Broadcast` and `BroadcastArray`.
+enum SinkState<E> {
+    /// Number of bytes the sink consumed ahead of the data reported as
+    /// written by the last `write`.
+    Attached(usize),
+    /// Detached by `BroadcastPolicy::Detach`. The error is gone once it was
+    /// returned because no sink was attached anymore.
+    Detached(Option<E>),
+}
+
+impl<E> SinkState<E> {
+    fn is_attached(&self) -> bool {
+        matches!(self, Self::Attached(_))
+    }
+
+    /// Bytes consumed ahead of the data reported as written, `None` for a
+    /// detached sink.
+    fn lead(&self) -> Option<usize> {
+        match self {
+            Self::Attached(lead) => Some(*lead),
+            Self::Detached(_) => None,
+        }
+    }
+
+    /// Hands the part of `buf` which `sink` did not consume yet to it. Stops
+    /// at `OpRes::Retry` and `OpRes::WouldBlock` of the sink and returns them.
+    fn write<W>(
+        &mut self,
+        sink: &mut W,
+        buf: &[u8],
+        policy: BroadcastPolicy,
+    ) -> Result<Option<OpRes>, E>
+    where
+        W: WriteCore<Err = E>,
+        E: From<UnexpectedEndOfFile>,
+    {
+        if let Self::Attached(pos) = self {
+            while *pos < buf.len() {
+                let res = match sink.write(&buf[*pos..]) {
+                    Ok(OpRes::Eof) | Ok(OpRes::Empty) => Err(E::from(UnexpectedEndOfFile)),
+                    Ok(res @ OpRes::Retry) | Ok(res @ OpRes::WouldBlock) => return Ok(Some(res)),
+                    Ok(OpRes::Partial(n)) | Ok(OpRes::Completly(n)) => Ok(n.get()),
+                    Err(err) => Err(err),
+                };
+                match res {
+                    Ok(n) => *pos += n,
+                    Err(err) => return self.fail(err, policy).map(|_| None),
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn flush<W>(&mut self, sink: &mut W, policy: BroadcastPolicy) -> Result<(), E>
+    where
+        W: WriteCore<Err = E>,
+    {
+        if self.is_attached() {
+            if let Err(err) = sink.flush() {
+                return self.fail(err, policy);
+            }
+        }
+        Ok(())
+    }
+
+    fn fail(&mut self, err: E, policy: BroadcastPolicy) -> Result<(), E> {
+        match policy {
+            BroadcastPolicy::Propagate => Err(err),
+            BroadcastPolicy::Detach => {
+                *self = Self::Detached(Some(err));
+                Ok(())
+            }
+        }
+    }
+
+    /// Marks the first `len` bytes as written by the whole broadcast. Every
+    /// attached sink consumed at least `len` bytes.
+    fn advance(&mut self, len: usize) {
+        if let Self::Attached(pos) = self {
+            *pos -= len;
+        }
+    }
+
+    fn take_error(&mut self) -> Option<E> {
+        match self {
+            Self::Attached(_) => None,
+            Self::Detached(err) => err.take(),
+        }
+    }
+
+    fn attach(&mut self) -> Option<E> {
+        let err = self.take_error();
+        if !self.is_attached() {
+            *self = Self::Attached(0);
+        }
+        err
+    }
+}
+
+/// Outcome of a `write` of `Broadcast` or `BroadcastArray` in which every
+/// attached sink consumed at least `common` bytes of the buffer. An error
+/// which happened after that progress is kept in `pending` for the next call.
+fn broadcast_res<E>(
+    len: NonZeroUsize,
+    common: usize,
+    blocked: Option<OpRes>,
+    error: Option<E>,
+    pending: &mut Option<E>,
+) -> Result<OpRes, E> {
+    match NonZeroUsize::new(common) {
+        Some(n) if n == len => Ok(OpRes::Completly(n)),
+        Some(n) => {
+            *pending = error;
+            Ok(OpRes::Partial(n))
+        }
+        None => match error {
+            Some(err) => Err(err),
+            None => Ok(blocked.unwrap_or(OpRes::Retry)),
+        },
+    }
+}
+
+/// Writer which writes all data into two sinks. `write` reports the part of
+/// the buffer which every attached sink consumed. A sink which got ahead
+/// skips the bytes it already consumed on the next `write`, so both sinks see
+/// the same bytes even if they report partial writes differently; a buffer
+/// shorter than those bytes is rejected with `Diverged`. `OpRes::Retry` and
+/// `OpRes::WouldBlock` of a sink are passed on. Nest it to write into more
+/// sinks of different types.
+pub struct Broadcast<A: WriteCore, B: WriteCore> {
+    first: A,
+    second: B,
+    policy: BroadcastPolicy,
+    first_state: SinkState<A::Err>,
+    second_state: SinkState<B::Err>,
+    error: Option<BroadcastError<A::Err, B::Err>>,
+}
+
+impl<A: WriteCore, B: WriteCore> Broadcast<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            policy: BroadcastPolicy::Propagate,
+            first_state: SinkState::Attached(0),
+            second_state: SinkState::Attached(0),
+            error: None,
+        }
+    }
+
+    pub fn policy(&self) -> BroadcastPolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: BroadcastPolicy) {
+        self.policy = policy;
+    }
+
+    /// Returns the errors of sinks detached by `BroadcastPolicy::Detach`,
+    /// unless they were already returned, and attaches the sinks again.
+    pub fn take_errors(&mut self) -> (Option<A::Err>, Option<B::Err>) {
+        (self.first_state.attach(), self.second_state.attach())
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.first, &mut self.second)
+    }
+
+    /// Whether no sink is attached anymore. Returns the kept errors the
+    /// first time this is noticed.
+    fn detached(&mut self) -> Result<bool, BroadcastError<A::Err, B::Err>> {
+        if self.first_state.is_attached() || self.second_state.is_attached() {
+            return Ok(false);
+        }
+        match (
+            self.first_state.take_error(),
+            self.second_state.take_error(),
+        ) {
+            (Some(first), Some(second)) => Err(BroadcastError::Both(first, second)),
+            (Some(first), None) => Err(BroadcastError::First(first)),
+            (None, Some(second)) => Err(BroadcastError::Second(second)),
+            (None, None) => Ok(true),
+        }
+    }
+}
+
+impl<A: WriteCore, B: WriteCore> WriteCore for Broadcast<A, B> {
+    type Err = BroadcastError<A::Err, B::Err>;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        let len = match NonZeroUsize::new(buf.len()) {
+            Some(len) => len,
+            None => return Ok(OpRes::Empty),
+        };
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        let (first, second) = (self.first_state.lead(), self.second_state.lead());
+        if first.into_iter().chain(second).any(|lead| lead > buf.len()) {
+            return Err(BroadcastError::Diverged);
+        }
+
+        let mut error = None;
+        let mut blocked = match self.first_state.write(&mut self.first, buf, self.policy) {
+            Ok(res) => res,
+            Err(err) => {
+                error = Some(BroadcastError::First(err));
+                None
+            }
+        };
+        if error.is_none() {
+            match self.second_state.write(&mut self.second, buf, self.policy) {
+                Ok(res) => blocked = blocked.or(res),
+                Err(err) => error = Some(BroadcastError::Second(err)),
+            }
+        }
+        if self.detached()? {
+            return Ok(OpRes::Eof);
+        }
+
+        let (first, second) = (self.first_state.lead(), self.second_state.lead());
+        let common = first.into_iter().chain(second).min().unwrap_or(0);
+        self.first_state.advance(common);
+        self.second_state.advance(common);
+        broadcast_res(len, common, blocked, error, &mut self.error)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.first_state
+            .flush(&mut self.first, self.policy)
+            .map_err(BroadcastError::First)?;
+        self.second_state
+            .flush(&mut self.second, self.policy)
+            .map_err(BroadcastError::Second)?;
+        self.detached().map(|_| ())
+    }
+}
+
+/// Error of a `BroadcastArray`. Errors created through the marker types are
+/// reported as `All` for every sink.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BroadcastArrayError<E, const N: usize> {
+    /// The sink at the index failed.
+    Sink(usize, E),
+    /// No sink is attached anymore. Carries the errors of the sinks, which
+    /// are `None` for errors that were already returned.
+    All([Option<E>; N]),
+    /// The buffer is shorter than the data a sink already consumed ahead of
+    /// what `write` reported as written.
+    Diverged,
+}
+
+impl<E: From<UnexpectedEndOfFile>, const N: usize> From<UnexpectedEndOfFile>
+    for BroadcastArrayError<E, N>
+{
+    fn from(err: UnexpectedEndOfFile) -> Self {
+        Self::All([(); N].map(|_| Some(E::from(err))))
+    }
+}
+
+impl<E: From<FormatterError>, const N: usize> From<FormatterError> for BroadcastArrayError<E, N> {
+    fn from(err: FormatterError) -> Self {
+        Self::All([(); N].map(|_| Some(E::from(err))))
+    }
+}
+
+impl<E: From<WouldBlock>, const N: usize> From<WouldBlock> for BroadcastArrayError<E, N> {
+    fn from(err: WouldBlock) -> Self {
+        Self::All([(); N].map(|_| Some(E::from(err))))
+    }
+}
+
+impl<E: ClassifyError, const N: usize> ClassifyError for BroadcastArrayError<E, N> {
+    fn kind(&self) -> CoreErrorKind {
+        match self {
+            Self::Sink(_, err) => err.kind(),
+            Self::All(errs) => errs
+                .iter()
+                .flatten()
+                .next()
+                .map_or(CoreErrorKind::Other, ClassifyError::kind),
+            Self::Diverged => CoreErrorKind::InvalidInput,
+        }
+    }
+}
+
+/// Same as `Broadcast`, but for `N` sinks of the same type.
+pub struct BroadcastArray<W: WriteCore, const N: usize> {
+    sinks: [W; N],
+    policy: BroadcastPolicy,
+    states: [SinkState<W::Err>; N],
+    error: Option<BroadcastArrayError<W::Err, N>>,
+}
+
+impl<W: WriteCore, const N: usize> BroadcastArray<W, N> {
+    pub fn new(sinks: [W; N]) -> Self {
+        Self {
+            sinks,
+            policy: BroadcastPolicy::Propagate,
+            states: [(); N].map(|_| SinkState::Attached(0)),
+            error: None,
+        }
+    }
+
+    pub fn policy(&self) -> BroadcastPolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: BroadcastPolicy) {
+        self.policy = policy;
+    }
+
+    /// Returns the error of the sink at `index` if it was detached by
+    /// `BroadcastPolicy::Detach`, unless it was already returned, and
+    /// attaches the sink again.
+    pub fn take_error(&mut self, index: usize) -> Option<W::Err> {
+        self.states.get_mut(index).and_then(SinkState::attach)
+    }
+
+    pub fn into_inner(self) -> [W; N] {
+        self.sinks
+    }
+
+    pub fn get_ref(&self) -> &[W; N] {
+        &self.sinks
+    }
+
+    pub fn get_mut(&mut self) -> &mut [W; N] {
+        &mut self.sinks
+    }
+
+    /// Whether no sink is attached anymore. Returns the kept errors the
+    /// first time this is noticed. Without any sinks, nothing is detached.
+    fn detached(&mut self) -> Result<bool, BroadcastArrayError<W::Err, N>> {
+        if N == 0 || self.states.iter().any(SinkState::is_attached) {
+            return Ok(false);
+        }
+        let errs = [(); N];
+        let mut states = self.states.iter_mut();
+        let errs = errs.map(|_| states.next().and_then(SinkState::take_error));
+        if errs.iter().all(Option::is_none) {
+            return Ok(true);
+        }
+        Err(BroadcastArrayError::All(errs))
+    }
+}
+
+impl<W: WriteCore, const N: usize> WriteCore for BroadcastArray<W, N> {
+    type Err = BroadcastArrayError<W::Err, N>;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        let len = match NonZeroUsize::new(buf.len()) {
+            Some(len) => len,
+            None => return Ok(OpRes::Empty),
+        };
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        let mut leads = self.states.iter().filter_map(SinkState::lead);
+        if leads.any(|lead| lead > buf.len()) {
+            return Err(BroadcastArrayError::Diverged);
+        }
+
+        let mut blocked = None;
+        let mut error = None;
+        for (i, (sink, state)) in self.sinks.iter_mut().zip(&mut self.states).enumerate() {
+            match state.write(sink, buf, self.policy) {
+                Ok(res) => blocked = blocked.or(res),
+                Err(err) => {
+                    error = Some(BroadcastArrayError::Sink(i, err));
+                    break;
+                }
+            }
+        }
+        if self.detached()? {
+            return Ok(OpRes::Eof);
+        }
+
+        let leads = self.states.iter().filter_map(SinkState::lead);
+        let common = leads.min().unwrap_or(len.get());
+        for state in &mut self.states {
+            state.advance(common);
+        }
+        broadcast_res(len, common, blocked, error, &mut self.error)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        for (i, (sink, state)) in self.sinks.iter_mut().zip(&mut self.states).enumerate() {
+            state
+                .flush(sink, self.policy)
+                .map_err(|err| BroadcastArrayError::Sink(i, err))?;
+        }
+        self.detached().map(|_| ())
+    }
+}
+
+pub struct BufReaderCore<R, const N: usize> {
+    inner: R,
+    buf: [u8; N],
+    pos: usize,
+    filled: usize,
+}
+
+impl<R, const N: usize> BufReaderCore<R, N> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: [0; N],
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.filled]
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    fn discard_buffer(&mut self) {
+        self.pos = 0;
+        self.filled = 0;
+    }
+}
+
+impl<R: fmt::Debug, const N: usize> fmt::Debug for BufReaderCore<R, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufReaderCore")
+            .field("reader", &self.inner)
+            .field("buffer", &format_args!("{}/{}", self.filled - self.pos, N))
+            .finish()
+    }
+}
+
+impl<E, R: ReadCore<Err = E>, const N: usize> ReadCore for BufReaderCore<R, N>
+where
+    E: From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock>,
+{
+    type Err = E;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, E> {
+        if buf.is_empty() {
+            return Ok(OpRes::Empty);
+        }
+        // Bypass the internal buffer for large reads if it is empty anyways
+        if self.pos == self.filled && buf.len() >= N {
+            self.discard_buffer();
+            return self.inner.read(buf);
+        }
+
+        let rem = match self.fill_buf()? {
+            FillRes::Data(rem) => rem,
+            FillRes::Retry => return Ok(OpRes::Retry),
+            FillRes::WouldBlock => return Ok(OpRes::WouldBlock),
+            FillRes::Eof => return Ok(OpRes::Eof),
+        };
+        let amt = cmp::min(rem.len(), buf.len());
+        buf[..amt].copy_from_slice(&rem[..amt]);
+        self.consume(amt);
+        Ok(NonZeroUsize::new(amt).map_or(OpRes::Eof, |n| {
+            if n.get() == buf.len() {
+                OpRes::Completly(n)
+            } else {
+                OpRes::Partial(n)
+            }
+        }))
+    }
+}
+
+impl<E, R: ReadCore<Err = E>, const N: usize> BufReadCore for BufReaderCore<R, N>
+where
+    E: From<InvalidUtf8> + From<UnexpectedEndOfFile> + From<WouldBlock>,
+{
+    fn fill_buf(&mut self) -> Result<FillRes<'_>, E> {
+        if self.pos == self.filled {
+            self.discard_buffer();
+            match self.inner.read(&mut self.buf)? {
+                OpRes::Retry => return Ok(FillRes::Retry),
+                OpRes::WouldBlock => return Ok(FillRes::WouldBlock),
+                OpRes::Eof | OpRes::Empty => return Ok(FillRes::Eof),
+                OpRes::Partial(n) | OpRes::Completly(n) => self.filled = n.get(),
+            }
+        }
+        Ok(FillRes::Data(&self.buf[self.pos..self.filled]))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = cmp::min(self.pos + amt, self.filled);
+    }
+}
+
+/// Seeking discards the internal buffer. `SeekFrom::Current` is relative to
+/// the logical position of the reader, not the one of the underlying reader.
+impl<E, R: SeekCore<Err = E>, const N: usize> SeekCore for BufReaderCore<R, N>
+where
+    E: From<OutOfBounds>,
+{
+    type Err = E;
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, E> {
+        let remainder = (self.filled - self.pos) as i64;
+        let res = if let SeekFrom::Current(n) = pos {
+            match n.checked_sub(remainder) {
+                Some(n) => self.inner.seek(SeekFrom::Current(n))?,
+                None => {
+                    self.inner.seek(SeekFrom::Current(-remainder))?;
+                    self.discard_buffer();
+                    self.inner.seek(SeekFrom::Current(n))?
+                }
+            }
+        } else {
+            self.inner.seek(pos)?
+        };
+        self.discard_buffer();
+        Ok(res)
+    }
+
+    fn stream_position(&mut self) -> Result<u64, E> {
+        let remainder = (self.filled - self.pos) as u64;
+        self.inner
+            .stream_position()?
+            .checked_sub(remainder)
+            .ok_or_else(|| E::from(OutOfBounds))
+    }
+}
+
+/// Action taken when a `BufWriterCore` is dropped with buffered data left or
+/// an `Lz4Writer` with a frame which was not finished.
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+pub enum DropPolicy {
+    /// Write the remaining data to the inner writer, which finishes the frame
+    /// of an `Lz4Writer`. Errors are ignored.
+    Flush,
+    /// Throw away the remaining data.
+    Discard,
+    /// Panic in debug builds and flush in release builds. Used to find code
+    /// paths which forget to call `flush`, `into_inner` or `finish`. With the
+    /// `std` feature, no panic is raised while the thread is already
+    /// unwinding.
+    PanicInDebug,
+}
+
+pub struct BufWriterCore<W: WriteCore, const N: usize> {
+    inner: W,
+    buf: [u8; N],
+    len: usize,
+    policy: DropPolicy,
+    // Prevents flushing the buffer twice if the inner writer panicked.
+    panicked: bool,
+}
+
+impl<W: WriteCore, const N: usize> BufWriterCore<W, N> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buf: [0; N],
+            len: 0,
+            policy: DropPolicy::Flush,
+            panicked: false,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    pub fn drop_policy(&self) -> DropPolicy {
+        self.policy
+    }
+
+    pub fn set_drop_policy(&mut self, policy: DropPolicy) {
+        self.policy = policy;
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Flushes the buffer and returns the inner writer. If flushing fails,
+    /// the writer is returned together with the error and the data which was
+    /// not written yet. Its drop policy is set to `DropPolicy::Discard`, so
+    /// dropping the error does not retry the flush.
+    pub fn into_inner(mut self) -> Result<W, IntoInnerError<Self, W::Err>> {
+        match self.flush_buf() {
+            Ok(()) => {
+                let this = ManuallyDrop::new(self);
+                Ok(unsafe { ptr::read(&this.inner) })
+            }
+            Err(error) => {
+                self.policy = DropPolicy::Discard;
+                Err(IntoInnerError {
+                    writer: self,
+                    error,
+                })
+            }
+        }
+    }
+
+    /// Writes the buffer to the inner writer. Bytes which were written are
+    /// removed from the buffer even if an error occurs, so a retried flush
+    /// continues where the last one stopped.
+    fn flush_buf(&mut self) -> Result<(), W::Err> {
+        let mut written = 0;
+        let res = loop {
+            if written >= self.len {
+                break Ok(());
+            }
+            self.panicked = true;
+            let res = self.inner.write(&self.buf[written..self.len]);
+            self.panicked = false;
+            match res {
+                Ok(OpRes::Retry) => {}
+                Ok(OpRes::Partial(n)) => written += n.get(),
+                Ok(OpRes::Completly(_)) => written = self.len,
+                Ok(OpRes::Eof) | Ok(OpRes::Empty) => break Err(W::Err::from(UnexpectedEndOfFile)),
+                Ok(OpRes::WouldBlock) => break Err(W::Err::from(WouldBlock)),
+                Err(err) => break Err(err),
+            }
+        };
+        self.buf.copy_within(written..self.len, 0);
+        self.len -= written;
+        res
+    }
+}
+
+impl<W: WriteCore + fmt::Debug, const N: usize> fmt::Debug for BufWriterCore<W, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufWriterCore")
+            .field("writer", &self.inner)
+            .field("buffer", &format_args!("{}/{}", self.len, N))
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl<W: WriteCore, const N: usize> WriteCore for BufWriterCore<W, N> {
+    type Err = W::Err;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        if buf.is_empty() {
+            return Ok(OpRes::Empty);
+        }
+        if self.len + buf.len() > N {
+            self.flush_buf()?;
+        }
+        if buf.len() >= N {
+            self.panicked = true;
+            let res = self.inner.write(buf);
+            self.panicked = false;
+            return res;
+        }
+
+        self.buf[self.len..self.len + buf.len()].copy_from_slice(buf);
+        self.len += buf.len();
+        Ok(NonZeroUsize::new(buf.len()).map_or(OpRes::Empty, OpRes::Completly))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        self.flush_buf()?;
+        self.inner.flush()
+    }
+}
+
+impl<W: WriteCore, const N: usize> Drop for BufWriterCore<W, N> {
+    fn drop(&mut self) {
+        if self.panicked || self.len == 0 {
+            return;
+        }
+        match self.policy {
+            DropPolicy::Flush => {
+                let _ = self.flush_buf();
+            }
+            DropPolicy::Discard => {}
+            DropPolicy::PanicInDebug => {
+                debug_assert!(panicking(), "BufWriterCore dropped with unflushed data");
+                let _ = self.flush_buf();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn panicking() -> bool {
+    std::thread::panicking()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn panicking() -> bool {
+    false
+}
+
+#[derive(Debug)]
+pub struct IntoInnerError<W, E> {
+    writer: W,
+    error: E,
+}
+
+impl<W, E> IntoInnerError<W, E> {
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    pub fn into_parts(self) -> (E, W) {
+        (self.error, self.writer)
+    }
+}
+
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct Cursor<T> {
+    inner: T,
+    pos: u64,
+}
+
+impl<T> Cursor<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner, pos: 0 }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    pub fn set_position(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+}
+
+impl<T: AsRef<[u8]>> Cursor<T> {
+    pub fn remaining_slice(&self) -> &[u8] {
+        let inner = self.inner.as_ref();
+        let start = cmp::min(self.pos, inner.len() as u64) as usize;
+        &inner[start..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.inner.as_ref().len() as u64
+    }
+}
+
+impl<T: AsRef<[u8]>> ReadCore for Cursor<T> {
+    type Err = CursorError;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
+        if buf.is_empty() {
+            return Ok(OpRes::Empty);
+        }
+        let rem = self.remaining_slice();
+        let amt = cmp::min(rem.len(), buf.len());
+        buf[..amt].copy_from_slice(&rem[..amt]);
+        self.pos += amt as u64;
+        Ok(NonZeroUsize::new(amt).map_or(OpRes::Eof, |n| {
+            if n.get() == buf.len() {
+                OpRes::Completly(n)
+            } else {
+                OpRes::Partial(n)
+            }
+        }))
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Self::Err> {
+        let rem = self.remaining_slice();
+        if rem.len() < buf.len() {
+            self.pos = self.inner.as_ref().len() as u64;
+            return Err(CursorError::UnexpectedEndOfFile);
+        }
+        buf.copy_from_slice(&rem[..buf.len()]);
+        self.pos += buf.len() as u64;
+        Ok(())
+    }
+}
+
+impl<T: AsRef<[u8]>> BufReadCore for Cursor<T> {
+    fn fill_buf(&mut self) -> Result<FillRes<'_>, Self::Err> {
+        let rem = self.remaining_slice();
+        Ok(if rem.is_empty() {
+            FillRes::Eof
+        } else {
+            FillRes::Data(rem)
+        })
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt as u64;
+    }
+}
+
+impl<T: AsRef<[u8]>> SeekCore for Cursor<T> {
+    type Err = CursorError;
+
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Err> {
+        self.pos = match pos {
+            SeekFrom::Start(n) => n,
+            SeekFrom::End(n) => seek_offset(self.inner.as_ref().len() as u64, n)?,
+            SeekFrom::Current(n) => seek_offset(self.pos, n)?,
+        };
+        Ok(self.pos)
+    }
+
+    fn stream_len(&mut self) -> Result<u64, Self::Err> {
+        Ok(self.inner.as_ref().len() as u64)
+    }
+
+    fn stream_position(&mut self) -> Result<u64, Self::Err> {
+        Ok(self.pos)
+    }
+}
+
+fn slice_write(pos_mut: &mut u64, slice: &mut [u8], buf: &[u8]) -> OpRes {
+    if buf.is_empty() {
+        return OpRes::Empty;
+    }
+    let pos = cmp::min(*pos_mut, slice.len() as u64) as usize;
+    let amt = cmp::min(slice.len() - pos, buf.len());
+    slice[pos..pos + amt].copy_from_slice(&buf[..amt]);
+    *pos_mut += amt as u64;
+    NonZeroUsize::new(amt).map_or(OpRes::Eof, |n| {
+        if n.get() == buf.len() {
+            OpRes::Completly(n)
+        } else {
+            OpRes::Partial(n)
+        }
+    })
+}
+
+impl WriteCore for Cursor<&mut [u8]> {
+    type Err = CursorError;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        Ok(slice_write(&mut self.pos, self.inner, buf))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
+impl<const N: usize> WriteCore for Cursor<[u8; N]> {
+    type Err = CursorError;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        Ok(slice_write(&mut self.pos, &mut self.inner, buf))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct ReadExact<'a, R: ?Sized> {
+    reader: &'a mut R,
+    buf: &'a mut [u8],
+}
+
+impl<R: AsyncReadCore + Unpin + ?Sized> Future for ReadExact<'_, R> {
+    type Output = Result<(), R::Err>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        while !this.buf.is_empty() {
+            let res = Pin::new(&mut *this.reader).poll_read(cx, this.buf);
+            match ready!(retry_to_pending(res, cx))? {
+                OpRes::Eof | OpRes::Empty => {
+                    return Poll::Ready(Err(R::Err::from(UnexpectedEndOfFile)))
+                }
+                OpRes::Retry | OpRes::WouldBlock => {}
+                OpRes::Partial(n) => this.buf = &mut mem::take(&mut this.buf)[n.get()..],
+                OpRes::Completly(_) => break,
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[derive(Debug)]
+pub struct WriteAll<'a, W: ?Sized> {
+    writer: &'a mut W,
+    buf: &'a [u8],
+}
+
+impl<W: AsyncWriteCore + Unpin + ?Sized> Future for WriteAll<'_, W> {
+    type Output = Result<(), W::Err>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        while !this.buf.is_empty() {
+            let res = Pin::new(&mut *this.writer).poll_write(cx, this.buf);
+            match ready!(retry_to_pending(res, cx))? {
+                OpRes::Eof | OpRes::Empty => {
+                    return Poll::Ready(Err(W::Err::from(UnexpectedEndOfFile)))
+                }
+                OpRes::Retry | OpRes::WouldBlock => {}
+                OpRes::Partial(n) => this.buf = &this.buf[n.get()..],
+                OpRes::Completly(_) => break,
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[derive(Debug)]
+pub struct Flush<'a, W: ?Sized> {
+    writer: &'a mut W,
+}
+
+impl<W: AsyncWriteCore + Unpin + ?Sized> Future for Flush<'_, W> {
+    type Output = Result<(), W::Err>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.writer).poll_flush(cx)
+    }
+}
+
+#[derive(Debug)]
+pub struct Close<'a, W: ?Sized> {
+    writer: &'a mut W,
+}
+
+impl<W: AsyncWriteCore + Unpin + ?Sized> Future for Close<'_, W> {
+    type Output = Result<(), W::Err>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.writer).poll_close(cx)
+    }
+}
+
+/// Exposes a blocking `ReadCore` or `WriteCore` as `AsyncReadCore` or
+/// `AsyncWriteCore`. `OpRes::Retry` and `OpRes::WouldBlock` are reported as
+/// `Poll::Pending` after waking the task, so the executor polls the operation again.
+///
+/// There is no way to register interest with the resource behind the inner
+/// type, so a task waiting on a resource which would block busy-spins until
+/// it becomes ready. Prefer a native `AsyncReadCore` or `AsyncWriteCore`
+/// implementation for resources which may stay unready for long.
+#[derive(Debug, Default)]
+pub struct FromBlocking<T> {
+    inner: T,
+}
+
+impl<T> FromBlocking<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<T: ReadCore + Unpin> AsyncReadCore for FromBlocking<T> {
+    type Err = T::Err;
+
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<OpRes, Self::Err>> {
+        retry_to_pending(Poll::Ready(self.inner.read(buf)), cx)
+    }
+}
+
+impl<T: WriteCore + Unpin> AsyncWriteCore for FromBlocking<T> {
+    type Err = T::Err;
+
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<OpRes, Self::Err>> {
+        retry_to_pending(Poll::Ready(self.inner.write(buf)), cx)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Err>> {
+        Poll::Ready(self.inner.flush())
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Err>> {
+        Poll::Ready(self.inner.flush())
+    }
+}
+
+/// Exposes an `AsyncReadCore` or `AsyncWriteCore` as blocking `ReadCore` or
+/// `WriteCore`. Every operation polls the inner type once with a waker that
+/// does nothing and reports `Poll::Pending` as `OpRes::WouldBlock`.
+/// Operations that cannot report that, like `flush`, poll until they
+/// complete.
+#[derive(Debug, Default)]
+pub struct Blocking<T> {
+    inner: T,
+}
+
+impl<T> Blocking<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+fn noop_waker() -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(ptr::null(), &VTABLE),
+        |_| {},
+        |_| {},
+        |_| {},
+    );
+    unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+}
+
+fn poll_once<T, F>(f: F) -> Poll<T>
+where
+    F: FnOnce(&mut Context<'_>) -> Poll<T>,
+{
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    f(&mut cx)
+}
+
+/// Drives `fut` to completion on the current thread.
+///
+/// The future is polled with a waker which does nothing, so a pending future
+/// is polled again right away. This busy-spins until it is ready and is only
+/// meant for futures which are expected to complete quickly.
+fn block_on<F: Future + Unpin>(mut fut: F) -> F::Output {
+    loop {
+        if let Poll::Ready(res) = poll_once(|cx| Pin::new(&mut fut).poll(cx)) {
+            return res;
+        }
+    }
+}
+
+impl<T: AsyncReadCore + Unpin> ReadCore for Blocking<T> {
+    type Err = T::Err;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
+        match poll_once(|cx| Pin::new(&mut self.inner).poll_read(cx, buf)) {
+            Poll::Ready(res) => res,
+            Poll::Pending => Ok(OpRes::WouldBlock),
+        }
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Self::Err> {
+        block_on(self.inner.read_exact(buf))
+    }
+}
+
+impl<T: AsyncWriteCore + Unpin> WriteCore for Blocking<T> {
+    type Err = T::Err;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        match poll_once(|cx| Pin::new(&mut self.inner).poll_write(cx, buf)) {
+            Poll::Ready(res) => res,
+            Poll::Pending => Ok(OpRes::WouldBlock),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        block_on(AsyncWriteCore::flush(&mut self.inner))
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Err> {
+        block_on(self.inner.write_all(buf))
+    }
+}
+
+/// A borrowed byte buffer which is incrementally filled and initialized.
+///
+/// The buffer is split into three regions: the filled part, which contains
+/// data written by a reader, the unfilled but initialized part, and the
+/// uninitialized part. Readers only ever see initialized memory, so no
+/// buffer has to be zeroed more than once.
+pub struct ReadBufCore<'a> {
+    buf: &'a mut [MaybeUninit<u8>],
+    filled: usize,
+    initialized: usize,
+}
+
+impl<'a> ReadBufCore<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        let len = buf.len();
+        Self {
+            buf: unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) },
+            filled: 0,
+            initialized: len,
+        }
+    }
+
+    pub fn uninit(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+        Self {
+            buf,
+            filled: 0,
+            initialized: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn filled(&self) -> &[u8] {
+        unsafe { &*(&self.buf[..self.filled] as *const [MaybeUninit<u8>] as *const [u8]) }
+    }
+
+    pub fn filled_mut(&mut self) -> &mut [u8] {
+        unsafe { &mut *(&mut self.buf[..self.filled] as *mut [MaybeUninit<u8>] as *mut [u8]) }
+    }
+
+    pub fn initialized(&self) -> &[u8] {
+        unsafe { &*(&self.buf[..self.initialized] as *const [MaybeUninit<u8>] as *const [u8]) }
+    }
+
+    pub fn initialized_len(&self) -> usize {
+        self.initialized
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.capacity() - self.filled
+    }
+
+    pub fn clear(&mut self) {
+        self.filled = 0;
+    }
+
+    /// Returns the unfilled part of the buffer, zeroing the uninitialized
+    /// bytes first if necessary.
+    pub fn initialize_unfilled(&mut self) -> &mut [u8] {
+        self.initialize_unfilled_to(self.remaining())
+    }
+
+    pub fn initialize_unfilled_to(&mut self, n: usize) -> &mut [u8] {
+        assert!(self.remaining() >= n, "n overflows remaining");
+        let end = self.filled + n;
+        if self.initialized < end {
+            for byte in &mut self.buf[self.initialized..end] {
+                *byte = MaybeUninit::new(0);
+            }
+            self.initialized = end;
+        }
+        unsafe { &mut *(&mut self.buf[self.filled..end] as *mut [MaybeUninit<u8>] as *mut [u8]) }
+    }
+
+    /// Returns the unfilled part of the buffer, which may be uninitialized.
+    ///
+    /// # Safety
+    ///
+    /// The caller must not de-initialize any bytes which were initialized.
+    pub unsafe fn unfilled_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        &mut self.buf[self.filled..]
+    }
+
+    /// Marks `n` additional bytes as filled. Panics if the bytes were not
+    /// initialized.
+    pub fn add_filled(&mut self, n: usize) {
+        self.set_filled(self.filled + n);
+    }
+
+    pub fn set_filled(&mut self, n: usize) {
+        assert!(
+            n <= self.initialized,
+            "filled must not become larger than initialized"
+        );
+        self.filled = n;
+    }
+
+    /// Asserts that the first `n` unfilled bytes are initialized.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that these bytes are in fact initialized.
+    pub unsafe fn assume_init(&mut self, n: usize) {
+        self.initialized = cmp::max(self.initialized, self.filled + n);
+    }
+
+    /// Copies `buf` behind the filled part. Panics if `buf` does not fit.
+    pub fn append(&mut self, buf: &[u8]) {
+        assert!(
+            self.remaining() >= buf.len(),
+            "buf.len() must fit in remaining()"
+        );
+        let end = self.filled + buf.len();
+        for (dst, src) in self.buf[self.filled..end].iter_mut().zip(buf) {
+            *dst = MaybeUninit::new(*src);
+        }
+        self.initialized = cmp::max(self.initialized, end);
+        self.filled = end;
+    }
+}
+
+impl fmt::Debug for ReadBufCore<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadBufCore")
+            .field("init", &self.initialized)
+            .field("filled", &self.filled)
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+/// Size of the stack buffer used by `copy`.
+pub const DEFAULT_BUF_SIZE: usize = 512;
+
+/// Error of `copy`, which tells on which side the transfer failed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CopyError<R, W> {
+    Read(R),
+    Write(W),
+}
+
+impl<E> CopyError<E, E> {
+    pub fn into_inner(self) -> E {
+        match self {
+            CopyError::Read(err) | CopyError::Write(err) => err,
+        }
+    }
+}
+
+/// Copies the entire content of `reader` into `writer` and returns the number
+/// of bytes copied. Uses a stack buffer of `DEFAULT_BUF_SIZE` bytes if neither
+/// side allows to skip it. `WouldBlock` of either side is returned as error.
+/// Buffered readers only consume what was written, otherwise data which was
+/// read but not written when the copy fails is lost; use `Copier` to resume
+/// the copy instead.
+pub fn copy<R, W>(reader: &mut R, writer: &mut W) -> Result<u64, CopyError<R::Err, W::Err>>
+where
+    R: ReadCore + ?Sized,
+    W: WriteCore + ?Sized,
+{
+    copy_buffered::<R, W, DEFAULT_BUF_SIZE>(reader, writer)
+}
+
+/// Same as `copy`, but uses a stack buffer of `N` bytes.
+pub fn copy_buffered<R, W, const N: usize>(
+    reader: &mut R,
+    writer: &mut W,
+) -> Result<u64, CopyError<R::Err, W::Err>>
+where
+    R: ReadCore + ?Sized,
+    W: WriteCore + ?Sized,
+{
+    CopySpec::copy_to::<N>(reader, writer)
+}
+
+/// Resumable copy through a stack buffer of `N` bytes. Data which was read
+/// but not accepted by the writer yet stays in the buffer when `copy` fails,
+/// so calling `copy` again after `WouldBlock` or another error continues
+/// without losing or repeating bytes.
+pub struct Copier<const N: usize> {
+    buf: [u8; N],
+    pos: usize,
+    filled: usize,
+    written: u64,
+}
+
+impl<const N: usize> Copier<N> {
+    pub fn new() -> Self {
+        Self {
+            buf: [0; N],
+            pos: 0,
+            filled: 0,
+            written: 0,
+        }
+    }
+
+    /// Data which was read, but not written yet.
+    pub fn pending(&self) -> &[u8] {
+        &self.buf[self.pos..self.filled]
+    }
+
+    /// Number of bytes written by this `Copier` so far.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Copies until `reader` reports its end and all data was written.
+    /// Returns the number of bytes written by this `Copier` so far.
+    pub fn copy<R, W>(
+        &mut self,
+        reader: &mut R,
+        writer: &mut W,
+    ) -> Result<u64, CopyError<R::Err, W::Err>>
+    where
+        R: ReadCore + ?Sized,
+        W: WriteCore + ?Sized,
+    {
+        loop {
+            while self.pos < self.filled {
+                let n = match writer.write(self.pending()).map_err(CopyError::Write)? {
+                    OpRes::Eof | OpRes::Empty => {
+                        return Err(CopyError::Write(W::Err::from(UnexpectedEndOfFile)))
+                    }
+                    OpRes::Retry => continue,
+                    OpRes::WouldBlock => return Err(CopyError::Write(W::Err::from(WouldBlock))),
+                    OpRes::Partial(n) | OpRes::Completly(n) => n.get(),
+                };
+                self.pos += n;
+                self.written += n as u64;
+            }
+            let len = match reader.read(&mut self.buf).map_err(CopyError::Read)? {
+                OpRes::Eof | OpRes::Empty => return Ok(self.written),
+                OpRes::Retry => continue,
+                OpRes::WouldBlock => return Err(CopyError::Read(R::Err::from(WouldBlock))),
+                OpRes::Partial(n) | OpRes::Completly(n) => n.get(),
+            };
+            self.pos = 0;
+            self.filled = len;
+        }
+    }
+}
+
+impl<const N: usize> Default for Copier<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Debug for Copier<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Copier")
+            .field("pending", &(self.filled - self.pos))
+            .field("written", &self.written)
+            .finish()
+    }
+}
+
+/// Entry point of `copy`, specialized in the std layer if both sides are
+/// legacy types.
+pub(crate) trait CopySpec<W: WriteCore + ?Sized>: ReadCore {
+    fn copy_to<const N: usize>(
+        &mut self,
+        writer: &mut W,
+    ) -> Result<u64, CopyError<Self::Err, W::Err>>;
+}
+
+impl<R: ReadCore + ?Sized, W: WriteCore + ?Sized> CopySpec<W> for R {
+    default_fn! {
+        fn copy_to<const N: usize>(
+            &mut self,
+            writer: &mut W,
+        ) -> Result<u64, CopyError<Self::Err, W::Err>> {
+            CopyRead::copy_into::<N>(self, writer)
+        }
+    }
+}
+
+/// Picks the way of reading depending on the capabilities of the reader.
+trait CopyRead<W: WriteCore + ?Sized>: ReadCore {
+    fn copy_into<const N: usize>(
+        &mut self,
+        writer: &mut W,
+    ) -> Result<u64, CopyError<Self::Err, W::Err>>;
+}
+
+impl<R: ReadCore + ?Sized, W: WriteCore + ?Sized> CopyRead<W> for R {
+    default_fn! {
+        fn copy_into<const N: usize>(
+            &mut self,
+            writer: &mut W,
+        ) -> Result<u64, CopyError<Self::Err, W::Err>> {
+            Copier::<N>::new().copy(self, writer)
+        }
+    }
+}
+
+#[cfg(not(feature = "stable"))]
+impl<R: BufReadCore + ?Sized, W: WriteCore + ?Sized> CopyRead<W> for R {
+    default_fn! {
+        fn copy_into<const N: usize>(
+            &mut self,
+            writer: &mut W,
+        ) -> Result<u64, CopyError<Self::Err, W::Err>> {
+            let mut written = 0;
+            loop {
+                let (n, res) = match self.fill_buf().map_err(CopyError::Read)? {
+                    FillRes::Data(buf) => write_all_counted(writer, buf),
+                    FillRes::Retry => continue,
+                    FillRes::WouldBlock => {
+                        return Err(CopyError::Read(Self::Err::from(WouldBlock)))
+                    }
+                    FillRes::Eof => return Ok(written),
+                };
+                self.consume(n);
+                written += n as u64;
+                res.map_err(CopyError::Write)?;
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "stable"))]
+impl<T: AsRef<[u8]>, W: WriteCore + ?Sized> CopyRead<W> for Cursor<T> {
+    fn copy_into<const N: usize>(
+        &mut self,
+        writer: &mut W,
+    ) -> Result<u64, CopyError<Self::Err, W::Err>> {
+        let (n, res) = write_all_counted(writer, self.remaining_slice());
+        self.pos += n as u64;
+        res.map_err(CopyError::Write)?;
+        Ok(n as u64)
+    }
+}
+
+/// Like `WriteCore::write_all`, but reports how many bytes were written
+/// before an error occurred.
+#[cfg(not(feature = "stable"))]
+pub(crate) fn write_all_counted<W: WriteCore + ?Sized>(
+    writer: &mut W,
+    buf: &[u8],
+) -> (usize, Result<(), W::Err>) {
+    let mut written = 0;
+    while written < buf.len() {
+        match writer.write(&buf[written..]) {
+            Ok(OpRes::Eof) | Ok(OpRes::Empty) => {
+                return (written, Err(W::Err::from(UnexpectedEndOfFile)))
+            }
+            Ok(OpRes::Retry) => {}
+            Ok(OpRes::WouldBlock) => return (written, Err(W::Err::from(WouldBlock))),
+            Ok(OpRes::Partial(n)) => written += n.get(),
+            Ok(OpRes::Completly(_)) => written = buf.len(),
+            Err(err) => return (written, Err(err)),
         }
     }
+    (written, Ok(()))
 }
@@ -0,0 +1,510 @@
+use super::core::{OpRes, ReadCore, WriteCore};
+use core::cmp;
+
+/// Checksum or hash computed incrementally over a stream of bytes.
+pub trait Digest {
+    type Output;
+
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the digest of all data passed to `update` since the last reset.
+    fn finish(&self) -> Self::Output;
+
+    fn reset(&mut self);
+}
+
+/// Reader which updates `digest` with all data read from `inner`.
+pub struct HashingReader<R, D> {
+    inner: R,
+    digest: D,
+}
+
+impl<R, D: Digest> HashingReader<R, D> {
+    pub fn new(inner: R, digest: D) -> Self {
+        Self { inner, digest }
+    }
+
+    pub fn finish(&self) -> D::Output {
+        self.digest.finish()
+    }
+
+    pub fn digest(&self) -> &D {
+        &self.digest
+    }
+
+    pub fn digest_mut(&mut self) -> &mut D {
+        &mut self.digest
+    }
+
+    pub fn into_inner(self) -> (R, D) {
+        (self.inner, self.digest)
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+}
+
+impl<R: ReadCore, D: Digest> ReadCore for HashingReader<R, D> {
+    type Err = R::Err;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
+        let res = self.inner.read(buf)?;
+        if let OpRes::Partial(n) | OpRes::Completly(n) = res {
+            self.digest.update(&buf[..n.get()]);
+        }
+        Ok(res)
+    }
+}
+
+/// Writer which updates `digest` with all data accepted by `inner`.
+pub struct HashingWriter<W, D> {
+    inner: W,
+    digest: D,
+}
+
+impl<W, D: Digest> HashingWriter<W, D> {
+    pub fn new(inner: W, digest: D) -> Self {
+        Self { inner, digest }
+    }
+
+    pub fn finish(&self) -> D::Output {
+        self.digest.finish()
+    }
+
+    pub fn digest(&self) -> &D {
+        &self.digest
+    }
+
+    pub fn digest_mut(&mut self) -> &mut D {
+        &mut self.digest
+    }
+
+    pub fn into_inner(self) -> (W, D) {
+        (self.inner, self.digest)
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+}
+
+impl<W: WriteCore, D: Digest> WriteCore for HashingWriter<W, D> {
+    type Err = W::Err;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        let res = self.inner.write(buf)?;
+        if let OpRes::Partial(n) | OpRes::Completly(n) = res {
+            self.digest.update(&buf[..n.get()]);
+        }
+        Ok(res)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        self.inner.flush()
+    }
+}
+
+const CRC16_TABLE: [u16; 256] = crc16_table();
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc16_table() -> [u16; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u16) << 8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// CRC-16/CCITT-FALSE: polynomial `0x1021`, initial value `0xFFFF`, no
+/// reflection and no final xor.
+#[derive(Copy, Clone, Debug)]
+pub struct Crc16 {
+    crc: u16,
+}
+
+impl Crc16 {
+    pub fn new() -> Self {
+        Self { crc: 0xFFFF }
+    }
+}
+
+impl Default for Crc16 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Crc16 {
+    type Output = u16;
+
+    fn update(&mut self, data: &[u8]) {
+        for b in data {
+            self.crc = (self.crc << 8) ^ CRC16_TABLE[((self.crc >> 8) as u8 ^ b) as usize];
+        }
+    }
+
+    fn finish(&self) -> u16 {
+        self.crc
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// CRC-32 as used by zlib, gzip and Ethernet.
+#[derive(Copy, Clone, Debug)]
+pub struct Crc32 {
+    crc: u32,
+}
+
+impl Crc32 {
+    pub fn new() -> Self {
+        Self { crc: !0 }
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Crc32 {
+    type Output = u32;
+
+    fn update(&mut self, data: &[u8]) {
+        for b in data {
+            self.crc = CRC32_TABLE[(self.crc as u8 ^ b) as usize] ^ (self.crc >> 8);
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        !self.crc
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+const ADLER32_MOD: u32 = 65521;
+// Largest number of bytes which can be summed up before `b` overflows
+const ADLER32_NMAX: usize = 5552;
+
+/// Adler-32 as used by zlib.
+#[derive(Copy, Clone, Debug)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    pub fn new() -> Self {
+        Self { a: 1, b: 0 }
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Adler32 {
+    type Output = u32;
+
+    fn update(&mut self, data: &[u8]) {
+        for chunk in data.chunks(ADLER32_NMAX) {
+            for b in chunk {
+                self.a += u32::from(*b);
+                self.b += self.a;
+            }
+            self.a %= ADLER32_MOD;
+            self.b %= ADLER32_MOD;
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+const SHA256_INIT: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+#[derive(Copy, Clone, Debug)]
+pub struct Sha256 {
+    state: [u32; 8],
+    block: [u8; 64],
+    block_len: usize,
+    len: u64,
+}
+
+impl Sha256 {
+    pub fn new() -> Self {
+        Self {
+            state: SHA256_INIT,
+            block: [0; 64],
+            block_len: 0,
+            len: 0,
+        }
+    }
+
+    fn compress(state: &mut [u32; 8], block: &[u8; 64]) {
+        let mut w = [0u32; 64];
+        for (w, chunk) in w.iter_mut().zip(block.chunks_exact(4)) {
+            *w = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+        for (k, w) in SHA256_K.iter().zip(&w) {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(*k)
+                .wrapping_add(*w);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *s = s.wrapping_add(v);
+        }
+    }
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Sha256 {
+    type Output = [u8; 32];
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.len += data.len() as u64;
+        while !data.is_empty() {
+            let n = cmp::min(64 - self.block_len, data.len());
+            self.block[self.block_len..self.block_len + n].copy_from_slice(&data[..n]);
+            self.block_len += n;
+            data = &data[n..];
+            if self.block_len == 64 {
+                Self::compress(&mut self.state, &self.block);
+                self.block_len = 0;
+            }
+        }
+    }
+
+    fn finish(&self) -> [u8; 32] {
+        let mut state = self.state;
+        let mut block = self.block;
+        block[self.block_len] = 0x80;
+        block[self.block_len + 1..].fill(0);
+        if self.block_len >= 56 {
+            Self::compress(&mut state, &block);
+            block = [0; 64];
+        }
+        block[56..].copy_from_slice(&(self.len * 8).to_be_bytes());
+        Self::compress(&mut state, &block);
+
+        let mut out = [0; 32];
+        for (out, s) in out.chunks_exact_mut(4).zip(&state) {
+            out.copy_from_slice(&s.to_be_bytes());
+        }
+        out
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+const XXH32_PRIME1: u32 = 0x9E37_79B1;
+const XXH32_PRIME2: u32 = 0x85EB_CA77;
+const XXH32_PRIME3: u32 = 0xC2B2_AE3D;
+const XXH32_PRIME4: u32 = 0x27D4_EB2F;
+const XXH32_PRIME5: u32 = 0x1656_67B1;
+
+/// xxHash32 as used by the LZ4 frame format.
+#[derive(Copy, Clone, Debug)]
+pub struct Xxh32 {
+    seed: u32,
+    acc: [u32; 4],
+    block: [u8; 16],
+    block_len: usize,
+    len: u64,
+}
+
+impl Xxh32 {
+    pub fn new() -> Self {
+        Self::with_seed(0)
+    }
+
+    pub fn with_seed(seed: u32) -> Self {
+        Self {
+            seed,
+            acc: [
+                seed.wrapping_add(XXH32_PRIME1).wrapping_add(XXH32_PRIME2),
+                seed.wrapping_add(XXH32_PRIME2),
+                seed,
+                seed.wrapping_sub(XXH32_PRIME1),
+            ],
+            block: [0; 16],
+            block_len: 0,
+            len: 0,
+        }
+    }
+
+    fn round(acc: u32, lane: &[u8]) -> u32 {
+        let lane = u32::from_le_bytes([lane[0], lane[1], lane[2], lane[3]]);
+        acc.wrapping_add(lane.wrapping_mul(XXH32_PRIME2))
+            .rotate_left(13)
+            .wrapping_mul(XXH32_PRIME1)
+    }
+
+    fn compress(acc: &mut [u32; 4], block: &[u8]) {
+        for (acc, lane) in acc.iter_mut().zip(block.chunks_exact(4)) {
+            *acc = Self::round(*acc, lane);
+        }
+    }
+}
+
+impl Default for Xxh32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Digest for Xxh32 {
+    type Output = u32;
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.len += data.len() as u64;
+        while !data.is_empty() {
+            let n = cmp::min(16 - self.block_len, data.len());
+            self.block[self.block_len..self.block_len + n].copy_from_slice(&data[..n]);
+            self.block_len += n;
+            data = &data[n..];
+            if self.block_len == 16 {
+                Self::compress(&mut self.acc, &self.block);
+                self.block_len = 0;
+            }
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        let mut hash = if self.len >= 16 {
+            let [a, b, c, d] = self.acc;
+            a.rotate_left(1)
+                .wrapping_add(b.rotate_left(7))
+                .wrapping_add(c.rotate_left(12))
+                .wrapping_add(d.rotate_left(18))
+        } else {
+            self.seed.wrapping_add(XXH32_PRIME5)
+        };
+        hash = hash.wrapping_add(self.len as u32);
+
+        let mut rest = &self.block[..self.block_len];
+        while rest.len() >= 4 {
+            let lane = u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]);
+            hash = hash
+                .wrapping_add(lane.wrapping_mul(XXH32_PRIME3))
+                .rotate_left(17)
+                .wrapping_mul(XXH32_PRIME4);
+            rest = &rest[4..];
+        }
+        for b in rest {
+            hash = hash
+                .wrapping_add(u32::from(*b).wrapping_mul(XXH32_PRIME5))
+                .rotate_left(11)
+                .wrapping_mul(XXH32_PRIME1);
+        }
+
+        hash ^= hash >> 15;
+        hash = hash.wrapping_mul(XXH32_PRIME2);
+        hash ^= hash >> 13;
+        hash = hash.wrapping_mul(XXH32_PRIME3);
+        hash ^ (hash >> 16)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::with_seed(self.seed);
+    }
+}
@@ -0,0 +1,642 @@
+use super::codec::CODEC_BUF_SIZE;
+use super::core::{written, InvalidEncoding, OpRes, ReadCore, UnexpectedEndOfFile};
+use super::digest::{Adler32, Crc32, Digest};
+use core::cmp;
+
+/// Size of the window `Inflate` needs to resolve back-references.
+pub const INFLATE_WINDOW_SIZE: usize = 32 * 1024;
+const INFLATE_WINDOW_MASK: usize = INFLATE_WINDOW_SIZE - 1;
+const INFLATE_MAX_MATCH: usize = 258;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+// Order in which the lengths of the code length code are stored
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+const GZIP_FHCRC: u8 = 0x02;
+const GZIP_FEXTRA: u8 = 0x04;
+const GZIP_FNAME: u8 = 0x08;
+const GZIP_FCOMMENT: u8 = 0x10;
+const GZIP_RESERVED: u8 = 0xE0;
+
+/// Canonical Huffman code, decoded one bit at a time.
+struct Huffman<const N: usize> {
+    // Number of symbols of each code length
+    count: [u16; 16],
+    // Symbols ordered by code
+    symbol: [u16; N],
+}
+
+impl<const N: usize> Huffman<N> {
+    fn new() -> Self {
+        Self {
+            count: [0; 16],
+            symbol: [0; N],
+        }
+    }
+
+    /// Builds the code from the code length of every symbol. Returns the
+    /// number of unused codes, which is negative if the code is
+    /// over-subscribed and positive if it is incomplete.
+    fn build(&mut self, lengths: &[u8]) -> i32 {
+        self.count = [0; 16];
+        for len in lengths {
+            self.count[usize::from(*len)] += 1;
+        }
+        if usize::from(self.count[0]) == lengths.len() {
+            return 0;
+        }
+
+        let mut left = 1;
+        for count in &self.count[1..] {
+            left = (left << 1) - i32::from(*count);
+            if left < 0 {
+                return left;
+            }
+        }
+
+        let mut offsets = [0; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + self.count[len];
+        }
+        for (symbol, len) in lengths.iter().enumerate() {
+            if *len != 0 {
+                let offset = &mut offsets[usize::from(*len)];
+                self.symbol[usize::from(*offset)] = symbol as u16;
+                *offset += 1;
+            }
+        }
+        left
+    }
+
+    /// Decodes a symbol from the lowest `available` bits of `bits`. Returns
+    /// the symbol and the number of bits used.
+    fn decode<E>(&self, mut bits: u64, available: u32) -> Result<(usize, u32), E>
+    where
+        E: From<InvalidEncoding> + From<UnexpectedEndOfFile>,
+    {
+        let (mut code, mut first, mut index) = (0, 0, 0);
+        for len in 1..16 {
+            if len > available {
+                return Err(E::from(UnexpectedEndOfFile));
+            }
+            code |= (bits & 1) as usize;
+            bits >>= 1;
+            let count = usize::from(self.count[len as usize]);
+            if code < first + count {
+                return Ok((usize::from(self.symbol[index + code - first]), len));
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(E::from(InvalidEncoding))
+    }
+}
+
+/// Container around the deflate data read by `Inflate`.
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+pub enum InflateFormat {
+    /// Deflate data without header and trailer (RFC 1951).
+    Raw,
+    /// Zlib stream with Adler-32 checksum (RFC 1950). Preset dictionaries are
+    /// not supported.
+    Zlib,
+    /// Single gzip member with CRC-32 checksum (RFC 1952).
+    Gzip,
+}
+
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+enum InflateState {
+    ZlibHeader,
+    GzipHeader,
+    GzipMtime,
+    GzipExtraLen,
+    GzipExtra(u16),
+    GzipName,
+    GzipComment,
+    GzipHeaderCrc,
+    BlockHeader,
+    StoredHeader,
+    Stored(u16),
+    DynamicHeader,
+    CodeLengthCodes(usize),
+    CodeLengths(usize),
+    Codes,
+    Trailer,
+    TrailerSize,
+    Done,
+}
+
+/// Reader which decompresses deflate data read from `inner`. All state lives
+/// in the struct and the caller-provided window, so no allocation is needed.
+/// Corrupt data and checksum mismatches fail with `InvalidEncoding`, a
+/// truncated stream with `UnexpectedEndOfFile`. Data is read from `inner` in
+/// chunks, so bytes following the compressed stream may be consumed.
+pub struct Inflate<'w, R> {
+    inner: R,
+    window: &'w mut [u8; INFLATE_WINDOW_SIZE],
+    format: InflateFormat,
+    state: InflateState,
+    // Position of the next byte in `window`
+    wpos: usize,
+    // Bytes at the end of `window` which were not read yet
+    pending: usize,
+    // Bytes of `window` which contain data
+    filled: usize,
+    last_block: bool,
+    gzip_flags: u8,
+    hlit: usize,
+    hdist: usize,
+    hclen: usize,
+    lengths: [u8; 320],
+    lencode: Huffman<19>,
+    litlen: Huffman<288>,
+    dist: Huffman<30>,
+    input: [u8; CODEC_BUF_SIZE],
+    in_pos: usize,
+    in_len: usize,
+    bitbuf: u64,
+    bitcnt: u32,
+    adler: Adler32,
+    crc: Crc32,
+    size: u32,
+}
+
+impl<'w, R: ReadCore> Inflate<'w, R> {
+    pub fn new(inner: R, window: &'w mut [u8; INFLATE_WINDOW_SIZE], format: InflateFormat) -> Self {
+        Self {
+            inner,
+            window,
+            format,
+            state: match format {
+                InflateFormat::Raw => InflateState::BlockHeader,
+                InflateFormat::Zlib => InflateState::ZlibHeader,
+                InflateFormat::Gzip => InflateState::GzipHeader,
+            },
+            wpos: 0,
+            pending: 0,
+            filled: 0,
+            last_block: false,
+            gzip_flags: 0,
+            hlit: 0,
+            hdist: 0,
+            hclen: 0,
+            lengths: [0; 320],
+            lencode: Huffman::new(),
+            litlen: Huffman::new(),
+            dist: Huffman::new(),
+            input: [0; CODEC_BUF_SIZE],
+            in_pos: 0,
+            in_len: 0,
+            bitbuf: 0,
+            bitcnt: 0,
+            adler: Adler32::new(),
+            crc: Crc32::new(),
+            size: 0,
+        }
+    }
+
+    pub fn zlib(inner: R, window: &'w mut [u8; INFLATE_WINDOW_SIZE]) -> Self {
+        Self::new(inner, window, InflateFormat::Zlib)
+    }
+
+    pub fn gzip(inner: R, window: &'w mut [u8; INFLATE_WINDOW_SIZE]) -> Self {
+        Self::new(inner, window, InflateFormat::Gzip)
+    }
+
+    pub fn format(&self) -> InflateFormat {
+        self.format
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads from `inner` until at least `bits` bits are buffered or the end
+    /// of file is reached. Returns the result of `inner` if it could not make
+    /// progress.
+    fn fill(&mut self, bits: u32) -> Result<Option<OpRes>, R::Err> {
+        while self.bitcnt < bits {
+            if self.in_pos == self.in_len {
+                match self.inner.read(&mut self.input)? {
+                    OpRes::Partial(n) | OpRes::Completly(n) => {
+                        self.in_pos = 0;
+                        self.in_len = n.get();
+                    }
+                    OpRes::Eof => break,
+                    res => return Ok(Some(res)),
+                }
+            }
+            self.bitbuf |= u64::from(self.input[self.in_pos]) << self.bitcnt;
+            self.in_pos += 1;
+            self.bitcnt += 8;
+        }
+        Ok(None)
+    }
+
+    /// Returns the `n` bits following the first `skip` buffered bits.
+    fn peek(&self, skip: u32, n: u32) -> Result<usize, R::Err>
+    where
+        R::Err: From<InvalidEncoding> + From<UnexpectedEndOfFile>,
+    {
+        if skip + n > self.bitcnt {
+            return Err(R::Err::from(UnexpectedEndOfFile));
+        }
+        Ok(((self.bitbuf >> skip) & ((1 << n) - 1)) as usize)
+    }
+
+    fn consume(&mut self, n: u32) {
+        self.bitbuf >>= n;
+        self.bitcnt -= n;
+    }
+
+    fn bits(&mut self, n: u32) -> usize {
+        let value = (self.bitbuf & ((1 << n) - 1)) as usize;
+        self.consume(n);
+        value
+    }
+
+    /// Drops the bits up to the next byte boundary.
+    fn align(&mut self) {
+        self.consume(self.bitcnt % 8);
+    }
+
+    fn push(&mut self, byte: u8) {
+        self.window[self.wpos] = byte;
+        self.wpos = (self.wpos + 1) & INFLATE_WINDOW_MASK;
+        self.pending += 1;
+        self.filled = cmp::min(self.filled + 1, INFLATE_WINDOW_SIZE);
+    }
+
+    fn end_of_block(&self) -> InflateState {
+        match (self.last_block, self.format) {
+            (false, _) => InflateState::BlockHeader,
+            (true, InflateFormat::Raw) => InflateState::Done,
+            (true, _) => InflateState::Trailer,
+        }
+    }
+
+    fn gzip_field(&self) -> InflateState {
+        if self.gzip_flags & GZIP_FEXTRA != 0 {
+            InflateState::GzipExtraLen
+        } else if self.gzip_flags & GZIP_FNAME != 0 {
+            InflateState::GzipName
+        } else if self.gzip_flags & GZIP_FCOMMENT != 0 {
+            InflateState::GzipComment
+        } else if self.gzip_flags & GZIP_FHCRC != 0 {
+            InflateState::GzipHeaderCrc
+        } else {
+            InflateState::BlockHeader
+        }
+    }
+
+    /// Builds the fixed codes of block type 1.
+    fn build_fixed(&mut self) {
+        let mut lengths = [0; 288];
+        lengths[..144].fill(8);
+        lengths[144..256].fill(9);
+        lengths[256..280].fill(7);
+        lengths[280..].fill(8);
+        self.litlen.build(&lengths);
+        self.dist.build(&[5; 30]);
+    }
+
+    /// Builds the codes of a dynamic block from `lengths`. Incomplete codes
+    /// are only allowed if they consist of a single code.
+    fn build_dynamic(&mut self) -> Result<(), InvalidEncoding> {
+        let (hlit, hdist) = (self.hlit, self.hdist);
+        if self.lengths[256] == 0 {
+            return Err(InvalidEncoding);
+        }
+        let left = self.litlen.build(&self.lengths[..hlit]);
+        if left < 0 || (left > 0 && hlit - usize::from(self.litlen.count[0]) != 1) {
+            return Err(InvalidEncoding);
+        }
+        let left = self.dist.build(&self.lengths[hlit..hlit + hdist]);
+        if left < 0 || (left > 0 && hdist - usize::from(self.dist.count[0]) != 1) {
+            return Err(InvalidEncoding);
+        }
+        Ok(())
+    }
+
+    /// Advances the state machine by one step, which consumes at most 48
+    /// bits and produces at most `INFLATE_MAX_MATCH` bytes. Stored blocks are
+    /// copied from the input buffer as far as the window has room for them.
+    /// Returns the result of `inner` if it could not provide enough data.
+    fn step(&mut self) -> Result<Option<OpRes>, R::Err>
+    where
+        R::Err: From<InvalidEncoding> + From<UnexpectedEndOfFile>,
+    {
+        macro_rules! need {
+            ($bits:expr) => {
+                if let Some(res) = self.fill($bits)? {
+                    return Ok(Some(res));
+                }
+                if self.bitcnt < $bits {
+                    return Err(R::Err::from(UnexpectedEndOfFile));
+                }
+            };
+        }
+        let invalid = || R::Err::from(InvalidEncoding);
+
+        self.state = match self.state {
+            InflateState::ZlibHeader => {
+                need!(16);
+                let cmf = self.bits(8);
+                let flg = self.bits(8);
+                if cmf & 0x0F != 8
+                    || cmf >> 4 > 7
+                    || !(cmf << 8 | flg).is_multiple_of(31)
+                    || flg & 0x20 != 0
+                {
+                    return Err(invalid());
+                }
+                InflateState::BlockHeader
+            }
+            InflateState::GzipHeader => {
+                need!(32);
+                let (id1, id2, cm) = (self.bits(8), self.bits(8), self.bits(8));
+                self.gzip_flags = self.bits(8) as u8;
+                if id1 != 0x1F || id2 != 0x8B || cm != 8 || self.gzip_flags & GZIP_RESERVED != 0 {
+                    return Err(invalid());
+                }
+                InflateState::GzipMtime
+            }
+            InflateState::GzipMtime => {
+                // MTIME, XFL and OS
+                need!(48);
+                self.consume(48);
+                self.gzip_field()
+            }
+            InflateState::GzipExtraLen => {
+                need!(16);
+                self.gzip_flags &= !GZIP_FEXTRA;
+                InflateState::GzipExtra(self.bits(16) as u16)
+            }
+            InflateState::GzipExtra(0) => self.gzip_field(),
+            InflateState::GzipExtra(n) => {
+                need!(8);
+                self.consume(8);
+                InflateState::GzipExtra(n - 1)
+            }
+            InflateState::GzipName | InflateState::GzipComment => {
+                need!(8);
+                if self.bits(8) != 0 {
+                    return Ok(None);
+                }
+                self.gzip_flags &= match self.state {
+                    InflateState::GzipName => !GZIP_FNAME,
+                    _ => !GZIP_FCOMMENT,
+                };
+                self.gzip_field()
+            }
+            InflateState::GzipHeaderCrc => {
+                need!(16);
+                self.consume(16);
+                self.gzip_flags &= !GZIP_FHCRC;
+                self.gzip_field()
+            }
+            InflateState::BlockHeader => {
+                need!(3);
+                self.last_block = self.bits(1) == 1;
+                match self.bits(2) {
+                    0 => {
+                        self.align();
+                        InflateState::StoredHeader
+                    }
+                    1 => {
+                        self.build_fixed();
+                        InflateState::Codes
+                    }
+                    2 => InflateState::DynamicHeader,
+                    _ => return Err(invalid()),
+                }
+            }
+            InflateState::StoredHeader => {
+                need!(32);
+                let len = self.bits(16) as u16;
+                if self.bits(16) as u16 != !len {
+                    return Err(invalid());
+                }
+                InflateState::Stored(len)
+            }
+            InflateState::Stored(0) => self.end_of_block(),
+            // The bit buffer holds whole bytes after the header, which are
+            // handed out first
+            InflateState::Stored(n) if self.bitcnt > 0 => {
+                let byte = self.bits(8) as u8;
+                self.push(byte);
+                InflateState::Stored(n - 1)
+            }
+            InflateState::Stored(n) if self.in_pos == self.in_len => {
+                need!(8);
+                InflateState::Stored(n)
+            }
+            InflateState::Stored(n) => {
+                let len = cmp::min(
+                    cmp::min(usize::from(n), self.in_len - self.in_pos),
+                    INFLATE_WINDOW_SIZE - self.pending,
+                );
+                let data = &self.input[self.in_pos..self.in_pos + len];
+                let first = cmp::min(len, INFLATE_WINDOW_SIZE - self.wpos);
+                self.window[self.wpos..self.wpos + first].copy_from_slice(&data[..first]);
+                self.window[..len - first].copy_from_slice(&data[first..]);
+                self.in_pos += len;
+                self.wpos = (self.wpos + len) & INFLATE_WINDOW_MASK;
+                self.pending += len;
+                self.filled = cmp::min(self.filled + len, INFLATE_WINDOW_SIZE);
+                InflateState::Stored(n - len as u16)
+            }
+            InflateState::DynamicHeader => {
+                need!(14);
+                self.hlit = self.bits(5) + 257;
+                self.hdist = self.bits(5) + 1;
+                self.hclen = self.bits(4) + 4;
+                if self.hlit > 286 || self.hdist > 30 {
+                    return Err(invalid());
+                }
+                self.lengths[..19].fill(0);
+                InflateState::CodeLengthCodes(0)
+            }
+            InflateState::CodeLengthCodes(i) if i == self.hclen => {
+                if self.lencode.build(&self.lengths[..19]) != 0 {
+                    return Err(invalid());
+                }
+                self.lengths = [0; 320];
+                InflateState::CodeLengths(0)
+            }
+            InflateState::CodeLengthCodes(i) => {
+                need!(3);
+                self.lengths[CODE_LENGTH_ORDER[i]] = self.bits(3) as u8;
+                InflateState::CodeLengthCodes(i + 1)
+            }
+            InflateState::CodeLengths(i) if i == self.hlit + self.hdist => {
+                self.build_dynamic()?;
+                InflateState::Codes
+            }
+            InflateState::CodeLengths(i) => {
+                if let Some(res) = self.fill(22)? {
+                    return Ok(Some(res));
+                }
+                let (symbol, used) = self.lencode.decode::<R::Err>(self.bitbuf, self.bitcnt)?;
+                let (len, base, extra) = match symbol {
+                    0..=15 => (symbol as u8, 1, 0),
+                    16 if i == 0 => return Err(invalid()),
+                    16 => (self.lengths[i - 1], 3, 2),
+                    17 => (0, 3, 3),
+                    _ => (0, 11, 7),
+                };
+                let repeat = base + self.peek(used, extra)?;
+                if i + repeat > self.hlit + self.hdist {
+                    return Err(invalid());
+                }
+                self.consume(used + extra);
+                self.lengths[i..i + repeat].fill(len);
+                InflateState::CodeLengths(i + repeat)
+            }
+            InflateState::Codes => {
+                if let Some(res) = self.fill(48)? {
+                    return Ok(Some(res));
+                }
+                let (symbol, mut used) = self.litlen.decode::<R::Err>(self.bitbuf, self.bitcnt)?;
+                match symbol {
+                    0..=255 => {
+                        self.consume(used);
+                        self.push(symbol as u8);
+                        InflateState::Codes
+                    }
+                    256 => {
+                        self.consume(used);
+                        self.end_of_block()
+                    }
+                    _ => {
+                        let symbol = symbol - 257;
+                        if symbol >= LENGTH_BASE.len() {
+                            return Err(invalid());
+                        }
+                        let len = usize::from(LENGTH_BASE[symbol])
+                            + self.peek(used, LENGTH_EXTRA[symbol])?;
+                        used += LENGTH_EXTRA[symbol];
+
+                        let (symbol, n) = self
+                            .dist
+                            .decode::<R::Err>(self.bitbuf >> used, self.bitcnt - used)?;
+                        used += n;
+                        if symbol >= DIST_BASE.len() {
+                            return Err(invalid());
+                        }
+                        let dist =
+                            usize::from(DIST_BASE[symbol]) + self.peek(used, DIST_EXTRA[symbol])?;
+                        used += DIST_EXTRA[symbol];
+                        if dist > self.filled {
+                            return Err(invalid());
+                        }
+
+                        self.consume(used);
+                        for _ in 0..len {
+                            let byte =
+                                self.window[self.wpos.wrapping_sub(dist) & INFLATE_WINDOW_MASK];
+                            self.push(byte);
+                        }
+                        InflateState::Codes
+                    }
+                }
+            }
+            InflateState::Trailer => {
+                self.align();
+                need!(32);
+                let value = self.bits(32) as u32;
+                let valid = match self.format {
+                    InflateFormat::Zlib => value.swap_bytes() == self.adler.finish(),
+                    _ => value == self.crc.finish(),
+                };
+                if !valid {
+                    return Err(invalid());
+                }
+                match self.format {
+                    InflateFormat::Gzip => InflateState::TrailerSize,
+                    _ => InflateState::Done,
+                }
+            }
+            InflateState::TrailerSize => {
+                need!(32);
+                if self.bits(32) as u32 != self.size {
+                    return Err(invalid());
+                }
+                InflateState::Done
+            }
+            InflateState::Done => InflateState::Done,
+        };
+        Ok(None)
+    }
+}
+
+impl<R: ReadCore> ReadCore for Inflate<'_, R>
+where
+    R::Err: From<InvalidEncoding> + From<UnexpectedEndOfFile>,
+{
+    type Err = R::Err;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
+        if buf.is_empty() {
+            return Ok(OpRes::Empty);
+        }
+        let want = cmp::min(buf.len(), INFLATE_WINDOW_SIZE - INFLATE_MAX_MATCH);
+        while self.pending < want {
+            // The trailer is checked once all data was read and hashed
+            let end = matches!(
+                self.state,
+                InflateState::Trailer | InflateState::TrailerSize | InflateState::Done
+            );
+            if self.state == InflateState::Done || (end && self.pending > 0) {
+                break;
+            }
+            if let Some(res) = self.step()? {
+                if self.pending > 0 {
+                    break;
+                }
+                return Ok(res);
+            }
+        }
+
+        let n = cmp::min(self.pending, buf.len());
+        let start = self.wpos.wrapping_sub(self.pending) & INFLATE_WINDOW_MASK;
+        let first = cmp::min(n, INFLATE_WINDOW_SIZE - start);
+        buf[..first].copy_from_slice(&self.window[start..start + first]);
+        buf[first..n].copy_from_slice(&self.window[..n - first]);
+        self.pending -= n;
+        match self.format {
+            InflateFormat::Raw => {}
+            InflateFormat::Zlib => self.adler.update(&buf[..n]),
+            InflateFormat::Gzip => {
+                self.crc.update(&buf[..n]);
+                self.size = self.size.wrapping_add(n as u32);
+            }
+        }
+        Ok(written(n, buf.len()))
+    }
+}
@@ -0,0 +1,201 @@
+//! Adapters exposing the core traits through the legacy `Read`, `Write` and
+//! `Seek` traits.
+
+use super::core::{
+    ClassifyError, FormatterError, InvalidUtf8, IoSliceCore, IoSliceMutCore, OpRes, OutOfBounds,
+    ReadBufCore, ReadCore, SeekCore, UnexpectedEndOfFile, WouldBlock, WriteCore,
+};
+use super::read::{Error as IoError, Read};
+use super::seek::Seek;
+use super::std::{ReadStd, WriteStd};
+use super::write::Write;
+use std::error::Error as StdError;
+use std::fmt;
+use std::io::{ErrorKind, IoSlice, IoSliceMut, SeekFrom};
+use std::string::String;
+use std::vec::Vec;
+
+#[derive(Debug)]
+pub struct LegacyError<E: fmt::Debug>(pub E);
+
+impl<E: fmt::Debug> StdError for LegacyError<E> {}
+
+impl<E: fmt::Debug> fmt::Display for LegacyError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unable to read from Reader. Error: {:?}", self.0)
+    }
+}
+
+/// Wraps a core error into an `IoError` with the matching kind.
+fn legacy_error<E>(err: E) -> IoError
+where
+    E: ClassifyError + fmt::Debug + Send + Sync + 'static,
+{
+    IoError::new(err.kind().into(), LegacyError(err))
+}
+
+/// Converts the result of a core operation into the byte count of a legacy
+/// one. `what` names the operation in the message of `OpRes::Retry`.
+fn op_res_to_io(res: OpRes, what: &str) -> Result<usize, IoError> {
+    match res {
+        OpRes::Retry => Err(IoError::new(
+            ErrorKind::Interrupted,
+            std::format!("{} was Interrupted. Try again.", what),
+        )),
+        OpRes::WouldBlock => Err(IoError::new(
+            ErrorKind::WouldBlock,
+            "Operation would block. Try again later.",
+        )),
+        OpRes::Partial(n) | OpRes::Completly(n) => Ok(n.get()),
+        OpRes::Eof | OpRes::Empty => Ok(0),
+    }
+}
+
+pub struct LegacyRead<R: ReadStd>(pub R);
+
+impl<Error, Reader> Read for LegacyRead<Reader>
+where
+    Error: ClassifyError
+        + fmt::Debug
+        + Send
+        + Sync
+        + 'static
+        + From<InvalidUtf8>
+        + From<UnexpectedEndOfFile>
+        + From<WouldBlock>,
+    Reader: ReadStd + ReadCore<Err = Error>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, IoError> {
+        self.0
+            .read(buf)
+            .map_err(legacy_error)
+            .and_then(|res| op_res_to_io(res, "Read"))
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize, IoError> {
+        IoSliceMutCore::from_std_slices(bufs, |bufs| ReadCore::read_vectored(&mut self.0, bufs))
+            .map_err(legacy_error)
+            .and_then(|res| op_res_to_io(res, "Read"))
+    }
+
+    fn is_read_vectored(&self) -> bool {
+        ReadCore::is_read_vectored(&self.0)
+    }
+
+    fn read_buf(&mut self, buf: &mut ReadBufCore<'_>) -> Result<(), IoError> {
+        self.0
+            .read_buf(buf)
+            .map_err(legacy_error)
+            .and_then(|res| op_res_to_io(res, "Read"))
+            .map(|_| ())
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize, IoError> {
+        self.0
+            .read_to_end(buf)
+            .map_err(legacy_error)
+            .map(|_| buf.len())
+    }
+
+    fn read_to_string(&mut self, buf: &mut String) -> Result<usize, IoError> {
+        self.0
+            .read_to_string(buf)
+            .map_err(legacy_error)
+            .map(|_| buf.len())
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), IoError> {
+        self.0.read_exact(buf).map_err(legacy_error)
+    }
+
+    fn by_ref(&mut self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct LegacyWrite<W: WriteStd>(pub W);
+
+impl<Error, Writer> Write for LegacyWrite<Writer>
+where
+    Error: ClassifyError
+        + fmt::Debug
+        + Send
+        + Sync
+        + 'static
+        + From<FormatterError>
+        + From<UnexpectedEndOfFile>
+        + From<WouldBlock>,
+    Writer: WriteStd + WriteCore<Err = Error>,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize, IoError> {
+        self.0
+            .write(buf)
+            .map_err(legacy_error)
+            .and_then(|res| op_res_to_io(res, "Write"))
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize, IoError> {
+        IoSliceCore::from_std_slices(bufs, |bufs| WriteCore::write_vectored(&mut self.0, bufs))
+            .map_err(legacy_error)
+            .and_then(|res| op_res_to_io(res, "Write"))
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        WriteCore::is_write_vectored(&self.0)
+    }
+
+    fn flush(&mut self) -> Result<(), IoError> {
+        self.0.flush().map_err(legacy_error)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), IoError> {
+        self.0.write_all(buf).map_err(legacy_error)
+    }
+
+    fn write_all_vectored(&mut self, bufs: &mut [IoSlice<'_>]) -> Result<(), IoError> {
+        IoSliceCore::from_std_slices(bufs, |bufs| {
+            WriteCore::write_all_vectored(&mut self.0, bufs)
+        })
+        .map_err(legacy_error)
+    }
+
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<(), IoError> {
+        self.0.write_fmt(fmt).map_err(legacy_error)
+    }
+
+    fn by_ref(&mut self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct LegacySeek<S: SeekCore>(pub S);
+
+impl<Error, Seeker> Seek for LegacySeek<Seeker>
+where
+    Error: ClassifyError + fmt::Debug + Send + Sync + 'static + From<OutOfBounds>,
+    Seeker: SeekCore<Err = Error>,
+{
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64, IoError> {
+        self.0.seek(pos.into()).map_err(legacy_error)
+    }
+
+    fn rewind(&mut self) -> Result<(), IoError> {
+        self.0.rewind().map_err(legacy_error)
+    }
+
+    fn stream_len(&mut self) -> Result<u64, IoError> {
+        self.0.stream_len().map_err(legacy_error)
+    }
+
+    fn stream_position(&mut self) -> Result<u64, IoError> {
+        self.0.stream_position().map_err(legacy_error)
+    }
+}
@@ -0,0 +1,827 @@
+use super::codec::CODEC_BUF_SIZE;
+use super::core::{
+    panicking, written, DropPolicy, InvalidEncoding, OpRes, ReadCore, UnexpectedEndOfFile,
+    WouldBlock, WriteCore,
+};
+use super::digest::{Digest, Xxh32};
+use core::cmp;
+use core::mem::ManuallyDrop;
+use core::ops::Range;
+use core::ptr;
+
+const LZ4_MAGIC: u32 = 0x184D_2204;
+const LZ4_SKIPPABLE_MAGIC: u32 = 0x184D_2A50;
+const LZ4_FLG_VERSION: u8 = 0x40;
+const LZ4_FLG_BLOCK_INDEPENDENCE: u8 = 0x20;
+const LZ4_FLG_BLOCK_CHECKSUM: u8 = 0x10;
+const LZ4_FLG_CONTENT_SIZE: u8 = 0x08;
+const LZ4_FLG_CONTENT_CHECKSUM: u8 = 0x04;
+const LZ4_FLG_DICT_ID: u8 = 0x01;
+// Block maximum size of 64 KiB
+const LZ4_BD_64K: u8 = 0x40;
+const LZ4_UNCOMPRESSED: u32 = 0x8000_0000;
+const LZ4_MIN_MATCH: usize = 4;
+// The last match must start at least 12 bytes before the end of a block and
+// the last 5 bytes are always literals.
+const LZ4_MF_LIMIT: usize = 12;
+const LZ4_LAST_LITERALS: usize = 5;
+const LZ4_HASH_LOG: u32 = 12;
+const LZ4_MAX_OFFSET: usize = 65535;
+/// Size of the window `Lz4Reader` needs to resolve matches.
+pub const LZ4_WINDOW_SIZE: usize = 64 * 1024;
+const LZ4_WINDOW_MASK: usize = LZ4_WINDOW_SIZE - 1;
+
+// Frame header, end of the frame, block size or the bytes around the literals
+// of a sequence. A literal or match length takes one byte per 255 of a block
+// of at most 64 KiB beyond the token.
+const LZ4_HEAD_SIZE: usize = 3 + 64 * 1024 / 255;
+
+/// Body of the block `Lz4Writer` writes after the block size in `head`.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Lz4Body {
+    None,
+    /// The first `len` bytes of the block, whose sequences are produced while
+    /// they are written.
+    Compressed(usize),
+    Uncompressed(usize),
+}
+
+/// Writer which compresses all data into `inner` using the LZ4 frame format.
+/// Data is collected in blocks of `N` bytes, at most 64 KiB, which are
+/// compressed independently. Call `finish` to end the frame, the drop policy
+/// decides what happens to a frame which was not finished.
+///
+/// Besides the block of `N` bytes, the writer holds a hash table of 8 KiB and
+/// the bytes of the sequence which is written. The block header in front of a
+/// compressed block contains its size, so each block is compressed twice:
+/// once to find its size and once more while its sequences are written to
+/// `inner`.
+pub struct Lz4Writer<W: WriteCore, const N: usize> {
+    inner: W,
+    block: [u8; N],
+    block_len: usize,
+    matcher: Lz4Matcher,
+    content_checksum: Option<Xxh32>,
+    started: bool,
+    // Written before `body` and between the literals of its sequences
+    head: [u8; LZ4_HEAD_SIZE],
+    head_pos: usize,
+    head_len: usize,
+    body: Lz4Body,
+    body_pos: usize,
+    // Literals of the current sequence which were not written yet and the
+    // match following them
+    literals: Range<usize>,
+    matched: Option<(usize, usize)>,
+    policy: DropPolicy,
+    // Prevents finishing the frame on drop if the inner writer panicked.
+    panicked: bool,
+}
+
+impl<W: WriteCore, const N: usize> Lz4Writer<W, N> {
+    /// Panics if `N` is zero or larger than 64 KiB.
+    pub fn new(inner: W) -> Self {
+        assert!(N > 0 && N <= 64 * 1024, "invalid LZ4 block size");
+        Self {
+            inner,
+            block: [0; N],
+            block_len: 0,
+            matcher: Lz4Matcher::new(),
+            content_checksum: None,
+            started: false,
+            head: [0; LZ4_HEAD_SIZE],
+            head_pos: 0,
+            head_len: 0,
+            body: Lz4Body::None,
+            body_pos: 0,
+            literals: 0..0,
+            matched: None,
+            policy: DropPolicy::Flush,
+            panicked: false,
+        }
+    }
+
+    /// Same as `new`, but the frame ends with a checksum of the content.
+    pub fn with_content_checksum(inner: W) -> Self {
+        let mut this = Self::new(inner);
+        this.content_checksum = Some(Xxh32::new());
+        this
+    }
+
+    /// With `DropPolicy::Flush` the frame is finished on drop.
+    pub fn drop_policy(&self) -> DropPolicy {
+        self.policy
+    }
+
+    pub fn set_drop_policy(&mut self, policy: DropPolicy) {
+        self.policy = policy;
+    }
+
+    /// Compresses the remaining data, writes the end of the frame and flushes
+    /// `inner`. Data written afterwards starts a new frame. If an error
+    /// occurs, calling `finish` again continues where it stopped.
+    pub fn finish(&mut self) -> Result<(), W::Err> {
+        // Only the end of the frame is left if a failed call wrote it to `head`
+        if self.started || self.head_pos == self.head_len {
+            self.flush_block()?;
+            self.start();
+            self.push_head(&0u32.to_le_bytes());
+            if let Some(checksum) = &mut self.content_checksum {
+                let value = checksum.finish();
+                checksum.reset();
+                self.push_head(&value.to_le_bytes());
+            }
+            self.started = false;
+        }
+        self.drain()?;
+        self.panicked = true;
+        let res = self.inner.flush();
+        self.panicked = false;
+        res
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the inner writer. Data which was not written yet is lost.
+    pub fn into_inner(self) -> W {
+        let this = ManuallyDrop::new(self);
+        unsafe { ptr::read(&this.inner) }
+    }
+
+    fn push_head(&mut self, bytes: &[u8]) {
+        self.head[self.head_len..self.head_len + bytes.len()].copy_from_slice(bytes);
+        self.head_len += bytes.len();
+    }
+
+    /// Adds the bytes which extend a literal or match length beyond 15.
+    fn push_length(&mut self, len: usize) {
+        if len < 15 {
+            return;
+        }
+        let mut len = len - 15;
+        while len >= 255 {
+            self.push_head(&[255]);
+            len -= 255;
+        }
+        self.push_head(&[len as u8]);
+    }
+
+    /// Adds the frame header to `head` if the frame was not started yet.
+    fn start(&mut self) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+        let mut flg = LZ4_FLG_VERSION | LZ4_FLG_BLOCK_INDEPENDENCE;
+        if self.content_checksum.is_some() {
+            flg |= LZ4_FLG_CONTENT_CHECKSUM;
+        }
+        let mut hc = Xxh32::new();
+        hc.update(&[flg, LZ4_BD_64K]);
+        self.push_head(&LZ4_MAGIC.to_le_bytes());
+        self.push_head(&[flg, LZ4_BD_64K, (hc.finish() >> 8) as u8]);
+    }
+
+    /// Writes `head` and `body` to `inner`. Bytes which were written are
+    /// removed even if an error occurs, so a retry continues where the last
+    /// attempt stopped.
+    fn drain(&mut self) -> Result<(), W::Err> {
+        loop {
+            let pending = if self.head_pos < self.head_len {
+                &self.head[self.head_pos..self.head_len]
+            } else {
+                match self.body {
+                    Lz4Body::None => break,
+                    Lz4Body::Compressed(_) => &self.block[self.literals.clone()],
+                    Lz4Body::Uncompressed(len) => &self.block[self.body_pos..len],
+                }
+            };
+            if pending.is_empty() {
+                self.next_part();
+                continue;
+            }
+            self.panicked = true;
+            let res = self.inner.write(pending);
+            self.panicked = false;
+            let n = match res? {
+                OpRes::Retry => continue,
+                OpRes::Partial(n) | OpRes::Completly(n) => n.get(),
+                OpRes::Eof | OpRes::Empty => return Err(W::Err::from(UnexpectedEndOfFile)),
+                OpRes::WouldBlock => return Err(W::Err::from(WouldBlock)),
+            };
+            if self.head_pos < self.head_len {
+                self.head_pos += n;
+            } else if let Lz4Body::Compressed(_) = self.body {
+                self.literals.start += n;
+            } else {
+                self.body_pos += n;
+            }
+        }
+        self.head_pos = 0;
+        self.head_len = 0;
+        self.body_pos = 0;
+        Ok(())
+    }
+
+    /// Fills `head` once it and the literals before it were written. It gets
+    /// the match of the current sequence or the token of the next one, whose
+    /// literals follow. The body ends after the last sequence.
+    fn next_part(&mut self) {
+        self.head_pos = 0;
+        self.head_len = 0;
+        let len = match self.body {
+            Lz4Body::Compressed(len) => len,
+            _ => {
+                self.body = Lz4Body::None;
+                return;
+            }
+        };
+        if let Some((offset, match_len)) = self.matched.take() {
+            self.push_head(&(offset as u16).to_le_bytes());
+            self.push_length(match_len - LZ4_MIN_MATCH);
+            return;
+        }
+        match self.matcher.next(&self.block[..len]) {
+            Some((literals, matched)) => {
+                let match_len = matched.map_or(0, |(_, len)| len - LZ4_MIN_MATCH);
+                let token = cmp::min(literals.len(), 15) << 4 | cmp::min(match_len, 15);
+                self.push_head(&[token as u8]);
+                self.push_length(literals.len());
+                self.literals = literals;
+                self.matched = matched;
+            }
+            None => self.body = Lz4Body::None,
+        }
+    }
+
+    /// Compresses the collected data into a block and writes it out.
+    fn flush_block(&mut self) -> Result<(), W::Err> {
+        self.drain()?;
+        if self.block_len == 0 {
+            return Ok(());
+        }
+        self.start();
+        let src = &self.block[..self.block_len];
+        let (size, body) = match self.matcher.compressed_size(src) {
+            Some(size) => {
+                self.matcher.start();
+                (size as u32, Lz4Body::Compressed(self.block_len))
+            }
+            None => (
+                self.block_len as u32 | LZ4_UNCOMPRESSED,
+                Lz4Body::Uncompressed(self.block_len),
+            ),
+        };
+        self.push_head(&size.to_le_bytes());
+        self.body = body;
+        self.literals = 0..0;
+        self.matched = None;
+        self.block_len = 0;
+        self.drain()
+    }
+}
+
+impl<W: WriteCore, const N: usize> WriteCore for Lz4Writer<W, N> {
+    type Err = W::Err;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        if buf.is_empty() {
+            return Ok(OpRes::Empty);
+        }
+        if self.block_len == N {
+            self.flush_block()?;
+        } else {
+            self.drain()?;
+        }
+        let n = cmp::min(buf.len(), N - self.block_len);
+        self.block[self.block_len..self.block_len + n].copy_from_slice(&buf[..n]);
+        self.block_len += n;
+        if let Some(checksum) = &mut self.content_checksum {
+            checksum.update(&buf[..n]);
+        }
+        Ok(written(n, buf.len()))
+    }
+
+    /// Ends the current block early, so all data written so far reaches
+    /// `inner`.
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        self.flush_block()?;
+        self.panicked = true;
+        let res = self.inner.flush();
+        self.panicked = false;
+        res
+    }
+}
+
+impl<W: WriteCore, const N: usize> Drop for Lz4Writer<W, N> {
+    fn drop(&mut self) {
+        // The frame is started once its first block is written and its end
+        // can still be pending afterwards
+        let unfinished = self.started || self.block_len > 0 || self.head_pos < self.head_len;
+        if self.panicked || !unfinished {
+            return;
+        }
+        match self.policy {
+            DropPolicy::Flush => {
+                let _ = self.finish();
+            }
+            DropPolicy::Discard => {}
+            DropPolicy::PanicInDebug => {
+                debug_assert!(panicking(), "Lz4Writer dropped without finishing the frame");
+                let _ = self.finish();
+            }
+        }
+    }
+}
+
+fn lz4_hash(seq: u32) -> usize {
+    (seq.wrapping_mul(2_654_435_761) >> (32 - LZ4_HASH_LOG)) as usize
+}
+
+fn lz4_read_u32(data: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]])
+}
+
+/// Number of bytes which extend a literal or match length `len` beyond 15.
+fn lz4_length_size(len: usize) -> usize {
+    if len < 15 {
+        0
+    } else {
+        (len - 15) / 255 + 1
+    }
+}
+
+/// Range of the literals of a sequence in its block and the match following
+/// them, given as offset and length. The last sequence of a block has no
+/// match.
+type Lz4Sequence = (Range<usize>, Option<(usize, usize)>);
+
+/// Splits a block into the sequences of its LZ4 compressed form.
+struct Lz4Matcher {
+    table: [u16; 1 << LZ4_HASH_LOG],
+    pos: usize,
+    anchor: usize,
+    done: bool,
+}
+
+impl Lz4Matcher {
+    fn new() -> Self {
+        Self {
+            table: [0; 1 << LZ4_HASH_LOG],
+            pos: 0,
+            anchor: 0,
+            done: false,
+        }
+    }
+
+    /// Starts a new block, which does not refer to the previous ones.
+    fn start(&mut self) {
+        self.table.fill(0);
+        self.pos = 0;
+        self.anchor = 0;
+        self.done = false;
+    }
+
+    /// Returns the next sequence of `src`, which has to be the same block
+    /// for all calls since `start`.
+    fn next(&mut self, src: &[u8]) -> Option<Lz4Sequence> {
+        if self.done {
+            return None;
+        }
+        if src.len() > LZ4_MF_LIMIT {
+            let limit = src.len() - LZ4_MF_LIMIT;
+            while self.pos < limit {
+                let i = self.pos;
+                let seq = lz4_read_u32(src, i);
+                let hash = lz4_hash(seq);
+                let candidate = usize::from(self.table[hash]);
+                self.table[hash] = i as u16;
+                if candidate >= i
+                    || i - candidate > LZ4_MAX_OFFSET
+                    || lz4_read_u32(src, candidate) != seq
+                {
+                    // Skip faster through data which does not compress
+                    self.pos += 1 + ((i - self.anchor) >> 6);
+                    continue;
+                }
+
+                let max = src.len() - LZ4_LAST_LITERALS - i;
+                let mut len = LZ4_MIN_MATCH;
+                while len < max && src[candidate + len] == src[i + len] {
+                    len += 1;
+                }
+                let literals = self.anchor..i;
+                self.pos = i + len;
+                self.anchor = self.pos;
+                return Some((literals, Some((i - candidate, len))));
+            }
+        }
+        self.done = true;
+        Some((self.anchor..src.len(), None))
+    }
+
+    /// Returns the size of the compressed block of `src` or `None` if it
+    /// would not be smaller than `src`.
+    fn compressed_size(&mut self, src: &[u8]) -> Option<usize> {
+        self.start();
+        let mut size = 0;
+        while let Some((literals, matched)) = self.next(src) {
+            size += 1 + lz4_length_size(literals.len()) + literals.len();
+            if let Some((_, len)) = matched {
+                size += 2 + lz4_length_size(len - LZ4_MIN_MATCH);
+            }
+            if size >= src.len() {
+                return None;
+            }
+        }
+        Some(size)
+    }
+}
+
+#[derive(Copy, PartialEq, Eq, Clone, Debug)]
+enum Lz4State {
+    Magic,
+    Skip(u32),
+    Descriptor,
+    BlockSize,
+    Uncompressed,
+    Token,
+    LiteralLen(usize),
+    Literals(usize),
+    Offset,
+    MatchLen(usize, usize),
+    Match(usize, usize),
+    BlockChecksum,
+    ContentChecksum,
+    FrameEnd,
+    Done,
+}
+
+/// Reader which decompresses LZ4 frames read from `inner`. Linked and
+/// independent blocks of every size are supported, the caller-provided window
+/// holds the data matches refer to. Block and content checksums as well as
+/// the content size are verified. Concatenated and skippable frames are
+/// supported. Corrupt data fails with `InvalidEncoding`, a truncated stream
+/// with `UnexpectedEndOfFile`.
+pub struct Lz4Reader<'w, R> {
+    inner: R,
+    window: &'w mut [u8; LZ4_WINDOW_SIZE],
+    state: Lz4State,
+    // Position of the next byte in `window`
+    wpos: usize,
+    // Bytes at the end of `window` which were not read yet
+    pending: usize,
+    // Bytes of `window` which contain data
+    filled: usize,
+    flags: u8,
+    block_max: usize,
+    block_left: usize,
+    match_nibble: usize,
+    block_checksum: Xxh32,
+    content_checksum: Xxh32,
+    content_size: Option<u64>,
+    read: u64,
+    frames: u64,
+    input: [u8; CODEC_BUF_SIZE],
+    in_pos: usize,
+    in_len: usize,
+}
+
+impl<'w, R: ReadCore> Lz4Reader<'w, R> {
+    pub fn new(inner: R, window: &'w mut [u8; LZ4_WINDOW_SIZE]) -> Self {
+        Self {
+            inner,
+            window,
+            state: Lz4State::Magic,
+            wpos: 0,
+            pending: 0,
+            filled: 0,
+            flags: 0,
+            block_max: 0,
+            block_left: 0,
+            match_nibble: 0,
+            block_checksum: Xxh32::new(),
+            content_checksum: Xxh32::new(),
+            content_size: None,
+            read: 0,
+            frames: 0,
+            input: [0; CODEC_BUF_SIZE],
+            in_pos: 0,
+            in_len: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads from `inner` until at least `n` bytes, at most 16, are buffered
+    /// or the end of file is reached. Returns the result of `inner` if it
+    /// could not make progress.
+    fn fill(&mut self, n: usize) -> Result<Option<OpRes>, R::Err> {
+        if self.in_len - self.in_pos >= n {
+            return Ok(None);
+        }
+        self.input.copy_within(self.in_pos..self.in_len, 0);
+        self.in_len -= self.in_pos;
+        self.in_pos = 0;
+        while self.in_len < n {
+            match self.inner.read(&mut self.input[self.in_len..])? {
+                OpRes::Partial(read) | OpRes::Completly(read) => self.in_len += read.get(),
+                OpRes::Eof => break,
+                res => return Ok(Some(res)),
+            }
+        }
+        Ok(None)
+    }
+
+    fn available(&self) -> usize {
+        self.in_len - self.in_pos
+    }
+
+    /// Consumes `n` buffered bytes of the current block.
+    fn take_block(&mut self, n: usize) -> Result<&[u8], InvalidEncoding> {
+        if n > self.block_left {
+            return Err(InvalidEncoding);
+        }
+        self.block_left -= n;
+        let data = &self.input[self.in_pos..self.in_pos + n];
+        self.in_pos += n;
+        if self.flags & LZ4_FLG_BLOCK_CHECKSUM != 0 {
+            self.block_checksum.update(data);
+        }
+        Ok(data)
+    }
+
+    fn take_u32(&mut self) -> u32 {
+        let value = lz4_read_u32(&self.input, self.in_pos);
+        self.in_pos += 4;
+        value
+    }
+
+    fn push(&mut self, byte: u8) {
+        self.window[self.wpos] = byte;
+        self.wpos = (self.wpos + 1) & LZ4_WINDOW_MASK;
+        self.pending += 1;
+        self.filled = cmp::min(self.filled + 1, LZ4_WINDOW_SIZE);
+    }
+
+    fn end_of_block(&self) -> Lz4State {
+        if self.flags & LZ4_FLG_BLOCK_CHECKSUM != 0 {
+            Lz4State::BlockChecksum
+        } else {
+            Lz4State::BlockSize
+        }
+    }
+
+    /// Advances the state machine by one step, which produces at most
+    /// `space` bytes. Returns the result of `inner` if it could not provide
+    /// enough data.
+    fn step(&mut self, space: usize) -> Result<Option<OpRes>, R::Err>
+    where
+        R::Err: From<InvalidEncoding> + From<UnexpectedEndOfFile>,
+    {
+        macro_rules! need {
+            ($n:expr) => {
+                if let Some(res) = self.fill($n)? {
+                    return Ok(Some(res));
+                }
+                if self.available() < $n {
+                    return Err(R::Err::from(UnexpectedEndOfFile));
+                }
+            };
+        }
+        let invalid = || R::Err::from(InvalidEncoding);
+
+        self.state = match self.state {
+            Lz4State::Magic => {
+                if let Some(res) = self.fill(4)? {
+                    return Ok(Some(res));
+                }
+                // Frames may be followed by further frames
+                if self.available() == 0 && self.frames > 0 {
+                    self.state = Lz4State::Done;
+                    return Ok(None);
+                }
+                need!(8);
+                match self.take_u32() {
+                    LZ4_MAGIC => Lz4State::Descriptor,
+                    magic if magic & 0xFFFF_FFF0 == LZ4_SKIPPABLE_MAGIC => {
+                        Lz4State::Skip(self.take_u32())
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+            Lz4State::Skip(0) => {
+                self.frames += 1;
+                Lz4State::Magic
+            }
+            Lz4State::Skip(n) => {
+                need!(1);
+                let skipped = cmp::min(n as usize, self.available());
+                self.in_pos += skipped;
+                Lz4State::Skip(n - skipped as u32)
+            }
+            Lz4State::Descriptor => {
+                need!(2);
+                let (flg, bd) = (self.input[self.in_pos], self.input[self.in_pos + 1]);
+                let len = if flg & LZ4_FLG_CONTENT_SIZE != 0 {
+                    11
+                } else {
+                    3
+                };
+                if flg & 0xC0 != LZ4_FLG_VERSION
+                    || flg & (0x02 | LZ4_FLG_DICT_ID) != 0
+                    || bd & 0x8F != 0
+                    || bd >> 4 < 4
+                {
+                    return Err(invalid());
+                }
+                need!(len);
+                let descriptor = &self.input[self.in_pos..self.in_pos + len];
+                let mut hc = Xxh32::new();
+                hc.update(&descriptor[..len - 1]);
+                if (hc.finish() >> 8) as u8 != descriptor[len - 1] {
+                    return Err(invalid());
+                }
+                self.content_size = (len == 11).then(|| {
+                    let mut size = [0; 8];
+                    size.copy_from_slice(&descriptor[2..10]);
+                    u64::from_le_bytes(size)
+                });
+                self.in_pos += len;
+                self.flags = flg;
+                self.block_max = 1 << (8 + 2 * (bd >> 4));
+                self.content_checksum.reset();
+                self.read = 0;
+                Lz4State::BlockSize
+            }
+            Lz4State::BlockSize => {
+                need!(4);
+                let size = self.take_u32();
+                if size == 0 {
+                    match self.flags & LZ4_FLG_CONTENT_CHECKSUM {
+                        0 => Lz4State::FrameEnd,
+                        _ => Lz4State::ContentChecksum,
+                    }
+                } else {
+                    self.block_left = (size & !LZ4_UNCOMPRESSED) as usize;
+                    self.block_checksum.reset();
+                    if self.block_left > self.block_max {
+                        return Err(invalid());
+                    }
+                    match size & LZ4_UNCOMPRESSED {
+                        0 => Lz4State::Token,
+                        _ => Lz4State::Uncompressed,
+                    }
+                }
+            }
+            Lz4State::Uncompressed if self.block_left == 0 => self.end_of_block(),
+            Lz4State::Uncompressed => {
+                need!(1);
+                let n = cmp::min(cmp::min(self.block_left, self.available()), space);
+                let start = self.in_pos;
+                self.take_block(n)?;
+                for i in start..start + n {
+                    self.push(self.input[i]);
+                }
+                Lz4State::Uncompressed
+            }
+            // A block ends with literals, but an empty block is fine
+            Lz4State::Token if self.block_left == 0 => self.end_of_block(),
+            Lz4State::Token => {
+                need!(1);
+                let token = usize::from(self.take_block(1)?[0]);
+                self.match_nibble = token & 0xF;
+                match token >> 4 {
+                    15 => Lz4State::LiteralLen(15),
+                    len => Lz4State::Literals(len),
+                }
+            }
+            Lz4State::LiteralLen(len) => {
+                need!(1);
+                match self.take_block(1)?[0] {
+                    255 => Lz4State::LiteralLen(len + 255),
+                    n => Lz4State::Literals(len + usize::from(n)),
+                }
+            }
+            Lz4State::Literals(0) if self.block_left == 0 => self.end_of_block(),
+            Lz4State::Literals(0) => Lz4State::Offset,
+            Lz4State::Literals(len) => {
+                need!(1);
+                let n = cmp::min(cmp::min(len, self.available()), space);
+                let start = self.in_pos;
+                self.take_block(n)?;
+                for i in start..start + n {
+                    self.push(self.input[i]);
+                }
+                Lz4State::Literals(len - n)
+            }
+            Lz4State::Offset => {
+                need!(2);
+                let bytes = self.take_block(2)?;
+                let offset = usize::from(u16::from_le_bytes([bytes[0], bytes[1]]));
+                if offset == 0 || offset > self.filled {
+                    return Err(invalid());
+                }
+                match self.match_nibble {
+                    15 => Lz4State::MatchLen(offset, 15 + LZ4_MIN_MATCH),
+                    len => Lz4State::Match(offset, len + LZ4_MIN_MATCH),
+                }
+            }
+            Lz4State::MatchLen(offset, len) => {
+                need!(1);
+                match self.take_block(1)?[0] {
+                    255 => Lz4State::MatchLen(offset, len + 255),
+                    n => Lz4State::Match(offset, len + usize::from(n)),
+                }
+            }
+            Lz4State::Match(_, 0) => Lz4State::Token,
+            Lz4State::Match(offset, len) => {
+                let n = cmp::min(len, space);
+                for _ in 0..n {
+                    let byte = self.window[self.wpos.wrapping_sub(offset) & LZ4_WINDOW_MASK];
+                    self.push(byte);
+                }
+                Lz4State::Match(offset, len - n)
+            }
+            Lz4State::BlockChecksum => {
+                need!(4);
+                if self.take_u32() != self.block_checksum.finish() {
+                    return Err(invalid());
+                }
+                Lz4State::BlockSize
+            }
+            Lz4State::ContentChecksum => {
+                need!(4);
+                if self.take_u32() != self.content_checksum.finish() {
+                    return Err(invalid());
+                }
+                Lz4State::FrameEnd
+            }
+            Lz4State::FrameEnd => {
+                if self.content_size.is_some_and(|size| size != self.read) {
+                    return Err(invalid());
+                }
+                self.frames += 1;
+                Lz4State::Magic
+            }
+            Lz4State::Done => Lz4State::Done,
+        };
+        Ok(None)
+    }
+}
+
+impl<R: ReadCore> ReadCore for Lz4Reader<'_, R>
+where
+    R::Err: From<InvalidEncoding> + From<UnexpectedEndOfFile>,
+{
+    type Err = R::Err;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
+        if buf.is_empty() {
+            return Ok(OpRes::Empty);
+        }
+        let want = cmp::min(buf.len(), LZ4_WINDOW_SIZE / 2);
+        while self.pending < want {
+            // Checksums are verified once all data of the frame was read
+            let end = matches!(
+                self.state,
+                Lz4State::ContentChecksum | Lz4State::FrameEnd | Lz4State::Done
+            );
+            if self.state == Lz4State::Done || (end && self.pending > 0) {
+                break;
+            }
+            if let Some(res) = self.step(want - self.pending)? {
+                if self.pending > 0 {
+                    break;
+                }
+                return Ok(res);
+            }
+        }
+
+        let n = cmp::min(self.pending, buf.len());
+        let start = self.wpos.wrapping_sub(self.pending) & LZ4_WINDOW_MASK;
+        let first = cmp::min(n, LZ4_WINDOW_SIZE - start);
+        buf[..first].copy_from_slice(&self.window[start..start + first]);
+        buf[first..n].copy_from_slice(&self.window[..n - first]);
+        self.pending -= n;
+        self.content_checksum.update(&buf[..n]);
+        self.read += n as u64;
+        Ok(written(n, buf.len()))
+    }
+}
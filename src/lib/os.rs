@@ -0,0 +1,195 @@
+//! Platform specific parts of the error handling. On Linux, error codes and
+//! messages follow the errno values of the kernel and glibc; elsewhere they
+//! are looked up through `std::io::Error`.
+
+use std::borrow::Cow;
+use std::io::ErrorKind;
+
+#[cfg(target_os = "linux")]
+extern "C" {
+    fn __errno_location() -> *mut i32;
+}
+
+/// Returns the last error reported by the platform for the current thread.
+#[cfg(target_os = "linux")]
+pub fn errno() -> i32 {
+    unsafe { *__errno_location() }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn errno() -> i32 {
+    std::io::Error::last_os_error().raw_os_error().unwrap_or(0)
+}
+
+#[cfg(target_os = "linux")]
+pub fn decode_error_kind(errno: i32) -> ErrorKind {
+    use ErrorKind::*;
+    match errno {
+        1 | 13 => PermissionDenied,
+        2 => NotFound,
+        4 => Interrupted,
+        7 => ArgumentListTooLong,
+        11 => WouldBlock,
+        12 => OutOfMemory,
+        16 => ResourceBusy,
+        17 => AlreadyExists,
+        18 => CrossesDevices,
+        20 => NotADirectory,
+        21 => IsADirectory,
+        22 => InvalidInput,
+        26 => ExecutableFileBusy,
+        27 => FileTooLarge,
+        28 => StorageFull,
+        29 => NotSeekable,
+        30 => ReadOnlyFilesystem,
+        31 => TooManyLinks,
+        32 => BrokenPipe,
+        35 => Deadlock,
+        36 => InvalidFilename,
+        38 | 95 => Unsupported,
+        39 => DirectoryNotEmpty,
+        98 => AddrInUse,
+        99 => AddrNotAvailable,
+        100 => NetworkDown,
+        101 => NetworkUnreachable,
+        103 => ConnectionAborted,
+        104 => ConnectionReset,
+        107 => NotConnected,
+        110 => TimedOut,
+        111 => ConnectionRefused,
+        113 => HostUnreachable,
+        116 => StaleNetworkFileHandle,
+        122 => QuotaExceeded,
+        _ => Other,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn decode_error_kind(errno: i32) -> ErrorKind {
+    std::io::Error::from_raw_os_error(errno).kind()
+}
+
+/// `strerror` style description of an error code.
+#[cfg(target_os = "linux")]
+pub fn error_string(errno: i32) -> Cow<'static, str> {
+    Cow::Borrowed(match errno {
+        0 => "Success",
+        1 => "Operation not permitted",
+        2 => "No such file or directory",
+        3 => "No such process",
+        4 => "Interrupted system call",
+        5 => "Input/output error",
+        6 => "No such device or address",
+        7 => "Argument list too long",
+        8 => "Exec format error",
+        9 => "Bad file descriptor",
+        10 => "No child processes",
+        11 => "Resource temporarily unavailable",
+        12 => "Cannot allocate memory",
+        13 => "Permission denied",
+        14 => "Bad address",
+        15 => "Block device required",
+        16 => "Device or resource busy",
+        17 => "File exists",
+        18 => "Invalid cross-device link",
+        19 => "No such device",
+        20 => "Not a directory",
+        21 => "Is a directory",
+        22 => "Invalid argument",
+        23 => "Too many open files in system",
+        24 => "Too many open files",
+        25 => "Inappropriate ioctl for device",
+        26 => "Text file busy",
+        27 => "File too large",
+        28 => "No space left on device",
+        29 => "Illegal seek",
+        30 => "Read-only file system",
+        31 => "Too many links",
+        32 => "Broken pipe",
+        33 => "Numerical argument out of domain",
+        34 => "Numerical result out of range",
+        35 => "Resource deadlock avoided",
+        36 => "File name too long",
+        37 => "No locks available",
+        38 => "Function not implemented",
+        39 => "Directory not empty",
+        40 => "Too many levels of symbolic links",
+        95 => "Operation not supported",
+        97 => "Address family not supported by protocol",
+        98 => "Address already in use",
+        99 => "Cannot assign requested address",
+        100 => "Network is down",
+        101 => "Network is unreachable",
+        103 => "Software caused connection abort",
+        104 => "Connection reset by peer",
+        105 => "No buffer space available",
+        106 => "Transport endpoint is already connected",
+        107 => "Transport endpoint is not connected",
+        110 => "Connection timed out",
+        111 => "Connection refused",
+        113 => "No route to host",
+        114 => "Operation already in progress",
+        115 => "Operation now in progress",
+        116 => "Stale file handle",
+        122 => "Disk quota exceeded",
+        _ => "Unknown error",
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn error_string(errno: i32) -> Cow<'static, str> {
+    // std formats OS errors as "<message> (os error <code>)", keep the message only
+    let message = std::format!("{}", std::io::Error::from_raw_os_error(errno));
+    let suffix = std::format!(" (os error {})", errno);
+    match message.strip_suffix(suffix.as_str()) {
+        Some(stripped) => Cow::Owned(stripped.into()),
+        None => Cow::Owned(message),
+    }
+}
+
+/// Description of an `ErrorKind`, used for errors without further details.
+pub fn kind_description(kind: ErrorKind) -> &'static str {
+    use ErrorKind::*;
+    match kind {
+        NotFound => "entity not found",
+        PermissionDenied => "permission denied",
+        ConnectionRefused => "connection refused",
+        ConnectionReset => "connection reset",
+        HostUnreachable => "host unreachable",
+        NetworkUnreachable => "network unreachable",
+        ConnectionAborted => "connection aborted",
+        NotConnected => "not connected",
+        AddrInUse => "address in use",
+        AddrNotAvailable => "address not available",
+        NetworkDown => "network down",
+        BrokenPipe => "broken pipe",
+        AlreadyExists => "entity already exists",
+        WouldBlock => "operation would block",
+        NotADirectory => "not a directory",
+        IsADirectory => "is a directory",
+        DirectoryNotEmpty => "directory not empty",
+        ReadOnlyFilesystem => "read-only filesystem or storage medium",
+        StaleNetworkFileHandle => "stale network file handle",
+        InvalidInput => "invalid input parameter",
+        InvalidData => "invalid data",
+        TimedOut => "timed out",
+        WriteZero => "write zero",
+        StorageFull => "no storage space",
+        NotSeekable => "seek on unseekable file",
+        QuotaExceeded => "quota exceeded",
+        FileTooLarge => "file too large",
+        ResourceBusy => "resource busy",
+        ExecutableFileBusy => "executable file busy",
+        Deadlock => "deadlock",
+        CrossesDevices => "cross-device link or rename",
+        TooManyLinks => "too many links",
+        InvalidFilename => "invalid filename",
+        ArgumentListTooLong => "argument list too long",
+        Interrupted => "operation interrupted",
+        Unsupported => "unsupported",
+        UnexpectedEof => "unexpected end of file",
+        OutOfMemory => "out of memory",
+        Other => "other error",
+        _ => "uncategorized error",
+    }
+}
@@ -1,11 +1,17 @@
 use super::core::{
-    Bytes, Chain, FormatterError, Initializer, InvalidUtf8, Take, UnexpectedEndOfFile,
+    Bytes, Chain, FormatterError, InvalidEncoding, InvalidUtf8, InvalidVarint, OutOfBounds,
+    ReadBufCore, Take, UnexpectedEndOfFile, WouldBlock,
 };
+use super::os;
+use std::borrow::Cow;
+use std::boxed::Box;
 use std::error;
 use std::fmt;
 use std::io::{ErrorKind, IoSliceMut};
 use std::result;
 use std::str;
+use std::string::String;
+use std::vec::Vec;
 
 pub type Result<T> = result::Result<T, Error>;
 
@@ -46,7 +52,7 @@ impl Error {
     }
 
     pub fn last_os_error() -> Error {
-        unimplemented!()
+        Error::from_raw_os_error(os::errno())
     }
 
     pub fn from_raw_os_error(code: i32) -> Error {
@@ -89,13 +95,21 @@ impl Error {
 
     pub fn kind(&self) -> ErrorKind {
         match self.repr {
-            Repr::Os(_code) => unimplemented!(),
+            Repr::Os(code) => os::decode_error_kind(code),
             Repr::Custom(ref c) => c.kind,
             Repr::Simple(kind) => kind,
         }
     }
 }
 
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Error {
+            repr: Repr::Simple(kind),
+        }
+    }
+}
+
 impl From<UnexpectedEndOfFile> for Error {
     fn from(_err: UnexpectedEndOfFile) -> Self {
         Error::new(ErrorKind::UnexpectedEof, "Unexpected EOF")
@@ -108,6 +122,33 @@ impl From<InvalidUtf8> for Error {
     }
 }
 
+impl From<InvalidVarint> for Error {
+    fn from(_err: InvalidVarint) -> Self {
+        Error::new(ErrorKind::InvalidData, "invalid LEB128 encoded integer")
+    }
+}
+
+impl From<InvalidEncoding> for Error {
+    fn from(_err: InvalidEncoding) -> Self {
+        Error::new(ErrorKind::InvalidData, "invalid encoded data")
+    }
+}
+
+impl From<OutOfBounds> for Error {
+    fn from(_err: OutOfBounds) -> Self {
+        Error::new(
+            ErrorKind::InvalidInput,
+            "invalid seek to a negative or overflowing position",
+        )
+    }
+}
+
+impl From<WouldBlock> for Error {
+    fn from(_err: WouldBlock) -> Self {
+        Error::new(ErrorKind::WouldBlock, "operation would block")
+    }
+}
+
 impl From<FormatterError> for Error {
     fn from(_err: FormatterError) -> Self {
         Error::new(ErrorKind::Other, "formatter error")
@@ -120,8 +161,8 @@ impl fmt::Debug for Repr {
             Repr::Os(code) => fmt
                 .debug_struct("Os")
                 .field("code", &code)
-                .field("kind", unimplemented!())
-                .field("message", unimplemented!())
+                .field("kind", &os::decode_error_kind(code))
+                .field("message", &os::error_string(code))
                 .finish(),
             Repr::Custom(ref c) => fmt::Debug::fmt(&c, fmt),
             Repr::Simple(kind) => fmt.debug_tuple("Kind").field(&kind).finish(),
@@ -132,9 +173,9 @@ impl fmt::Debug for Repr {
 impl fmt::Display for Error {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.repr {
-            Repr::Os(_code) => unimplemented!(),
+            Repr::Os(code) => write!(fmt, "{} (os error {})", os::error_string(code), code),
             Repr::Custom(ref c) => c.error.fmt(fmt),
-            Repr::Simple(_kind) => unimplemented!(),
+            Repr::Simple(kind) => fmt.write_str(os::kind_description(kind)),
         }
     }
 }
@@ -143,7 +184,11 @@ impl error::Error for Error {
     #[allow(deprecated, deprecated_in_future)]
     fn description(&self) -> &str {
         match self.repr {
-            Repr::Os(..) | Repr::Simple(..) => unimplemented!(),
+            Repr::Os(code) => match os::error_string(code) {
+                Cow::Borrowed(message) => message,
+                Cow::Owned(_) => os::kind_description(os::decode_error_kind(code)),
+            },
+            Repr::Simple(kind) => os::kind_description(kind),
             Repr::Custom(ref c) => c.error.description(),
         }
     }
@@ -204,36 +249,30 @@ where
     F: FnMut(&R) -> usize,
 {
     let start_len = buf.len();
-    let mut g = Guard {
-        len: buf.len(),
-        buf,
-    };
-    let ret;
+    let mut initialized = 0;
     loop {
-        if g.len == g.buf.len() {
-            unsafe {
-                g.buf.reserve(reservation_size(r));
-                let capacity = g.buf.capacity();
-                g.buf.set_len(capacity);
-                r.initializer().initialize(&mut g.buf[g.len..]);
-            }
+        if buf.len() == buf.capacity() {
+            buf.reserve(reservation_size(r));
         }
 
-        match r.read(&mut g.buf[g.len..]) {
-            Ok(0) => {
-                ret = Ok(g.len - start_len);
-                break;
-            }
-            Ok(n) => g.len += n,
-            Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
-            Err(e) => {
-                ret = Err(e);
-                break;
-            }
+        let mut read_buf = ReadBufCore::uninit(buf.spare_capacity_mut());
+        unsafe {
+            read_buf.assume_init(initialized);
+        }
+        match r.read_buf(&mut read_buf) {
+            Ok(()) => {}
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+        let filled = read_buf.filled().len();
+        if filled == 0 {
+            return Ok(buf.len() - start_len);
+        }
+        initialized = read_buf.initialized_len() - filled;
+        unsafe {
+            buf.set_len(buf.len() + filled);
         }
     }
-
-    ret
 }
 
 fn append_to_string<F>(buf: &mut String, f: F) -> Result<usize>
@@ -271,9 +310,10 @@ pub trait Read {
         false
     }
 
-    #[inline]
-    unsafe fn initializer(&self) -> Initializer {
-        Initializer::zeroing()
+    fn read_buf(&mut self, buf: &mut ReadBufCore<'_>) -> Result<()> {
+        let n = self.read(buf.initialize_unfilled())?;
+        buf.add_filled(n);
+        Ok(())
     }
 
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
@@ -334,3 +374,9 @@ pub trait Read {
         Take::new(self, limit)
     }
 }
+
+pub trait BufRead: Read {
+    fn fill_buf(&mut self) -> Result<&[u8]>;
+
+    fn consume(&mut self, amt: usize);
+}
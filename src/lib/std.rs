@@ -1,40 +1,34 @@
 use super::alloc::ReadAlloc;
-use super::core::{Bytes, Initializer, OpRes, ReadCore, Take, UnexpectedEndOfFile, WriteCore};
-use super::read::{Error, Read};
+#[cfg(not(feature = "stable"))]
+use super::core::{write_all_counted, CopyError, CopySpec};
+use super::core::{
+    BufReadCore, Bytes, ClassifyError, CoreErrorKind, FillRes, IoSliceCore, IoSliceMutCore, OpRes,
+    ReadBufCore, ReadCore, SeekCore, SeekFrom, Take, WriteCore,
+};
+use super::read::{BufRead, Error, Read};
+use super::seek::Seek;
 use super::write::Write;
+use core::any::Any;
 use core::num::NonZeroUsize;
+use core::slice;
 use std::fmt;
-use std::io::{ErrorKind, IoSlice, IoSliceMut};
+use std::io::{self, ErrorKind, IoSlice, IoSliceMut};
+use std::string::String;
+use std::vec::Vec;
 
 pub trait ReadStd: ReadAlloc {
-    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<OpRes, Self::Err>;
-
-    fn is_read_vectored(&self) -> bool;
-}
-
-impl<T: ?Sized> ReadStd for T
-where
-    T: ReadAlloc,
-{
-    default fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<OpRes, Self::Err> {
-        default_read_vectored(|b| self.read(b), bufs)
+    #[deprecated(note = "use `ReadCore::read_vectored` with `IoSliceMutCore`")]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<OpRes, Self::Err> {
+        IoSliceMutCore::from_std_slices(bufs, |bufs| ReadCore::read_vectored(self, bufs))
     }
 
-    default fn is_read_vectored(&self) -> bool {
-        false
+    #[deprecated(note = "use `ReadCore::is_read_vectored`")]
+    fn is_read_vectored(&self) -> bool {
+        ReadCore::is_read_vectored(self)
     }
 }
 
-pub(crate) fn default_read_vectored<F, E>(read: F, bufs: &mut [IoSliceMut<'_>]) -> Result<OpRes, E>
-where
-    F: FnOnce(&mut [u8]) -> Result<OpRes, E>,
-{
-    let buf = bufs
-        .iter_mut()
-        .find(|b| !b.is_empty())
-        .map_or(&mut [][..], |b| &mut **b);
-    read(buf)
-}
+impl<T: ?Sized> ReadStd for T where T: ReadAlloc {}
 
 impl<T: ?Sized> ReadCore for T
 where
@@ -42,203 +36,767 @@ where
 {
     type Err = Error;
 
-    default fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
-        match Read::read(self, buf) {
-            Ok(0) => Ok(OpRes::Eof),
-            Ok(n) if n == buf.len() => unsafe {
-                Ok(OpRes::Completly(NonZeroUsize::new_unchecked(n)))
-            },
-            Ok(n) => unsafe { Ok(OpRes::Partial(NonZeroUsize::new_unchecked(n))) },
-            Err(e) if e.kind() == ErrorKind::Interrupted => Ok(OpRes::Retry),
-            Err(e) => Err(e),
+    default_fn! {
+        fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
+            match Read::read(self, buf) {
+                Ok(0) if buf.is_empty() => Ok(OpRes::Empty),
+                Ok(0) => Ok(OpRes::Eof),
+                Ok(n) if n == buf.len() => unsafe {
+                    Ok(OpRes::Completly(NonZeroUsize::new_unchecked(n)))
+                },
+                Ok(n) => unsafe { Ok(OpRes::Partial(NonZeroUsize::new_unchecked(n))) },
+                Err(e) if e.kind() == ErrorKind::Interrupted => Ok(OpRes::Retry),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(OpRes::WouldBlock),
+                Err(e) => Err(e),
+            }
         }
     }
 
-    default unsafe fn initializer(&self) -> Initializer {
-        Read::initializer(self)
+    default_fn! {
+        fn read_vectored(
+            &mut self,
+            bufs: &mut [IoSliceMutCore<'_>],
+        ) -> Result<OpRes, Self::Err> {
+            let full_len = bufs.iter().map(|b| b.len()).sum();
+            match IoSliceMutCore::as_std_slices(bufs, |bufs| Read::read_vectored(self, bufs)) {
+                Ok(0) if full_len == 0 => Ok(OpRes::Empty),
+                Ok(0) => Ok(OpRes::Eof),
+                Ok(n) => unsafe {
+                    Ok(if n == full_len {
+                        OpRes::Completly(NonZeroUsize::new_unchecked(n))
+                    } else {
+                        OpRes::Partial(NonZeroUsize::new_unchecked(n))
+                    })
+                },
+                Err(e) if e.kind() == ErrorKind::Interrupted => Ok(OpRes::Retry),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(OpRes::WouldBlock),
+                Err(e) => Err(e),
+            }
+        }
     }
 
-    default fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Self::Err> {
-        Read::read_exact(self, buf).map(|_| ())
+    default_fn! {
+        fn is_read_vectored(&self) -> bool {
+            Read::is_read_vectored(self)
+        }
     }
 
-    #[must_use]
-    default fn by_ref(&mut self) -> &mut Self
-    where
-        Self: Sized,
-    {
-        Read::by_ref(self)
+    default_fn! {
+        fn read_buf(&mut self, buf: &mut ReadBufCore<'_>) -> Result<OpRes, Self::Err> {
+            let start = buf.filled().len();
+            let remaining = buf.remaining();
+            match Read::read_buf(self, buf) {
+                Ok(()) => unsafe {
+                    let n = buf.filled().len() - start;
+                    Ok(match n {
+                        0 if remaining == 0 => OpRes::Empty,
+                        0 => OpRes::Eof,
+                        n if n == remaining => OpRes::Completly(NonZeroUsize::new_unchecked(n)),
+                        n => OpRes::Partial(NonZeroUsize::new_unchecked(n)),
+                    })
+                },
+                Err(e) if e.kind() == ErrorKind::Interrupted => Ok(OpRes::Retry),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(OpRes::WouldBlock),
+                Err(e) => Err(e),
+            }
+        }
+    }
+
+    default_fn! {
+        fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Self::Err> {
+            Read::read_exact(self, buf).map(|_| ())
+        }
+    }
+
+    default_fn! {
+        fn by_ref(&mut self) -> &mut Self
+        where
+            Self: Sized,
+        {
+            Read::by_ref(self)
+        }
     }
 
-    default fn bytes(self) -> Bytes<Self>
-    where
-        Self: Sized,
-    {
-        Read::bytes(self)
+    default_fn! {
+        fn bytes(self) -> Bytes<Self>
+        where
+            Self: Sized,
+        {
+            Read::bytes(self)
+        }
     }
 
-    default fn take(self, limit: u64) -> Take<Self>
-    where
-        Self: Sized,
-    {
-        Read::take(self, limit)
+    default_fn! {
+        fn take(self, limit: u64) -> Take<Self>
+        where
+            Self: Sized,
+        {
+            Read::take(self, limit)
+        }
     }
 }
 
+#[cfg(not(feature = "stable"))]
 impl<T: ?Sized> ReadAlloc for T
 where
     T: Read + ReadCore<Err = Error>,
 {
-    default fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<(), Self::Err> {
-        Read::read_to_end(self, buf).map(|_| ())
+    default_fn! {
+        fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<(), Self::Err> {
+            Read::read_to_end(self, buf).map(|_| ())
+        }
     }
 
-    default fn read_to_string(&mut self, buf: &mut String) -> Result<(), Self::Err> {
-        Read::read_to_string(self, buf).map(|_| ())
+    default_fn! {
+        fn read_to_string(&mut self, buf: &mut String) -> Result<(), Self::Err> {
+            Read::read_to_string(self, buf).map(|_| ())
+        }
     }
 }
 
-impl<T: ?Sized> ReadStd for T
+impl<T: ?Sized> BufReadCore for T
 where
-    T: Read + ReadCore<Err = Error>,
+    T: BufRead + ReadCore<Err = Error>,
 {
-    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<OpRes, Self::Err> {
-        match Read::read_vectored(self, bufs) {
-            Ok(0) => Ok(OpRes::Eof),
-            Ok(n) => unsafe {
-                let full_len = bufs.iter().map(|b| b.len()).sum();
-                Ok(if n == full_len {
-                    OpRes::Completly(NonZeroUsize::new_unchecked(n))
-                } else {
-                    OpRes::Partial(NonZeroUsize::new_unchecked(n))
-                })
-            },
-            Err(e) if e.kind() == ErrorKind::Interrupted => Ok(OpRes::Retry),
-            Err(e) => Err(e),
+    default_fn! {
+        fn fill_buf(&mut self) -> Result<FillRes<'_>, Self::Err> {
+            match BufRead::fill_buf(self) {
+                Ok([]) => Ok(FillRes::Eof),
+                Ok(buf) => Ok(FillRes::Data(buf)),
+                Err(e) if e.kind() == ErrorKind::Interrupted => Ok(FillRes::Retry),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(FillRes::WouldBlock),
+                Err(e) => Err(e),
+            }
         }
     }
 
-    fn is_read_vectored(&self) -> bool {
-        Read::is_read_vectored(self)
+    default_fn! {
+        fn consume(&mut self, amt: usize) {
+            BufRead::consume(self, amt)
+        }
     }
 }
 
 pub trait WriteStd: WriteCore {
-    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<OpRes, Self::Err>;
+    #[deprecated(note = "use `WriteCore::write_vectored` with `IoSliceCore`")]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<OpRes, Self::Err> {
+        IoSliceCore::from_std_slices(bufs, |bufs| WriteCore::write_vectored(self, bufs))
+    }
 
-    fn is_write_vectored(&self) -> bool;
+    #[deprecated(note = "use `WriteCore::is_write_vectored`")]
+    fn is_write_vectored(&self) -> bool {
+        WriteCore::is_write_vectored(self)
+    }
 
-    fn write_all_vectored(&mut self, bufs: &mut [IoSlice<'_>]) -> Result<(), Self::Err>;
+    #[deprecated(note = "use `WriteCore::write_all_vectored` with `IoSliceCore`")]
+    fn write_all_vectored(&mut self, bufs: &mut [IoSlice<'_>]) -> Result<(), Self::Err> {
+        IoSliceCore::from_std_slices(bufs, |bufs| WriteCore::write_all_vectored(self, bufs))
+    }
 }
 
-impl<T: ?Sized> WriteStd for T
+impl<T: ?Sized> WriteStd for T where T: WriteCore {}
+
+impl<T> WriteCore for T
 where
-    T: WriteCore,
+    T: Write,
 {
-    default fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<OpRes, Self::Err> {
-        default_write_vectored(|b| self.write(b), bufs)
-    }
-
-    default fn is_write_vectored(&self) -> bool {
-        false
-    }
-
-    default fn write_all_vectored(
-        &mut self,
-        mut bufs: &mut [IoSlice<'_>],
-    ) -> Result<(), Self::Err> {
-        bufs = IoSlice::advance(bufs, 0);
-        while !bufs.is_empty() {
-            match self.write_vectored(bufs)? {
-                OpRes::Eof => return Err(Self::Err::from(UnexpectedEndOfFile)),
-                OpRes::Retry => {}
-                OpRes::Completly(n) => {
-                    IoSlice::advance(bufs, n.get());
-                    break;
-                }
-                OpRes::Partial(n) => {
-                    IoSlice::advance(bufs, n.get());
-                }
+    type Err = Error;
+
+    default_fn! {
+        fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+            match Write::write(self, buf) {
+                Ok(0) if buf.is_empty() => Ok(OpRes::Empty),
+                Ok(0) => Ok(OpRes::Eof),
+                Ok(n) if n == buf.len() => unsafe {
+                    Ok(OpRes::Completly(NonZeroUsize::new_unchecked(n)))
+                },
+                Ok(n) => unsafe { Ok(OpRes::Partial(NonZeroUsize::new_unchecked(n))) },
+                Err(e) if e.kind() == ErrorKind::Interrupted => Ok(OpRes::Retry),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(OpRes::WouldBlock),
+                Err(e) => Err(e),
+            }
+        }
+    }
+
+    default_fn! {
+        fn write_vectored(&mut self, bufs: &[IoSliceCore<'_>]) -> Result<OpRes, Self::Err> {
+            let full_len = bufs.iter().map(|b| b.len()).sum();
+            match IoSliceCore::as_std_slices(bufs, |bufs| Write::write_vectored(self, bufs)) {
+                Ok(0) if full_len == 0 => Ok(OpRes::Empty),
+                Ok(0) => Ok(OpRes::Eof),
+                Ok(n) => unsafe {
+                    Ok(if n == full_len {
+                        OpRes::Completly(NonZeroUsize::new_unchecked(n))
+                    } else {
+                        OpRes::Partial(NonZeroUsize::new_unchecked(n))
+                    })
+                },
+                Err(e) if e.kind() == ErrorKind::Interrupted => Ok(OpRes::Retry),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(OpRes::WouldBlock),
+                Err(e) => Err(e),
             }
         }
-        Ok(())
+    }
+
+    default_fn! {
+        fn is_write_vectored(&self) -> bool {
+            Write::is_write_vectored(self)
+        }
+    }
+
+    default_fn! {
+        fn flush(&mut self) -> Result<(), Self::Err> {
+            Write::flush(self)
+        }
+    }
+
+    default_fn! {
+        fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Err> {
+            Write::write_all(self, buf)
+        }
+    }
+
+    default_fn! {
+        fn write_all_vectored(
+            &mut self,
+            bufs: &mut [IoSliceCore<'_>],
+        ) -> Result<(), Self::Err> {
+            IoSliceCore::as_std_slices(bufs, |bufs| Write::write_all_vectored(self, bufs))
+        }
+    }
+
+    default_fn! {
+        fn write_fmt(&mut self, fmt: fmt::Arguments) -> Result<(), Self::Err> {
+            Write::write_fmt(self, fmt)
+        }
+    }
+
+    default_fn! {
+        fn by_ref(&mut self) -> &mut Self
+        where
+            Self: Sized,
+        {
+            Write::by_ref(self)
+        }
     }
 }
 
-impl<T> WriteCore for T
+// The conversions between core and std slices go through the pointer and the
+// length of each slice, so they do not depend on the layout of `IoSlice` and
+// `IoSliceMut` on any platform.
+
+impl<'a> From<IoSlice<'a>> for IoSliceCore<'a> {
+    fn from(buf: IoSlice<'a>) -> Self {
+        let buf: &[u8] = &buf;
+        // `IoSlice<'a>` borrows its bytes for `'a`, not just for the deref.
+        IoSliceCore::new(unsafe { slice::from_raw_parts(buf.as_ptr(), buf.len()) })
+    }
+}
+
+impl<'a> From<IoSliceCore<'a>> for IoSlice<'a> {
+    fn from(buf: IoSliceCore<'a>) -> Self {
+        IoSlice::new(buf.as_slice())
+    }
+}
+
+impl<'a> From<IoSliceMut<'a>> for IoSliceMutCore<'a> {
+    fn from(mut buf: IoSliceMut<'a>) -> Self {
+        let buf: &mut [u8] = &mut buf;
+        // `IoSliceMut<'a>` borrows its bytes for `'a`, not just for the deref.
+        IoSliceMutCore::new(unsafe { slice::from_raw_parts_mut(buf.as_mut_ptr(), buf.len()) })
+    }
+}
+
+impl<'a> From<IoSliceMutCore<'a>> for IoSliceMut<'a> {
+    fn from(buf: IoSliceMutCore<'a>) -> Self {
+        IoSliceMut::new(buf.into_slice())
+    }
+}
+
+/// Number of slices the vectored conversions keep on the stack, longer lists
+/// are collected into a `Vec`.
+const STACK_SLICES: usize = 8;
+
+fn with_converted<T, R>(
+    bufs: impl ExactSizeIterator<Item = T>,
+    empty: impl FnMut() -> T,
+    f: impl FnOnce(&mut [T]) -> R,
+) -> R {
+    let len = bufs.len();
+    if len > STACK_SLICES {
+        return f(&mut bufs.collect::<Vec<_>>());
+    }
+    let mut empty = empty;
+    let mut stack = [(); STACK_SLICES].map(|_| empty());
+    for (slot, buf) in stack.iter_mut().zip(bufs) {
+        *slot = buf;
+    }
+    f(&mut stack[..len])
+}
+
+impl<'a> IoSliceCore<'a> {
+    /// Calls `f` with copies of the std slices `bufs`.
+    pub fn from_std_slices<R>(bufs: &[IoSlice<'a>], f: impl FnOnce(&mut [Self]) -> R) -> R {
+        with_converted(
+            bufs.iter().map(|buf| Self::from(*buf)),
+            || Self::new(&[]),
+            f,
+        )
+    }
+
+    /// Calls `f` with std copies of the slices `bufs`.
+    pub fn as_std_slices<R>(bufs: &[Self], f: impl FnOnce(&mut [IoSlice<'a>]) -> R) -> R {
+        with_converted(
+            bufs.iter().map(|buf| IoSlice::from(*buf)),
+            || IoSlice::new(&[]),
+            f,
+        )
+    }
+}
+
+impl<'a> IoSliceMutCore<'a> {
+    /// Calls `f` with slices reborrowing the std slices `bufs`.
+    pub fn from_std_slices<'b, R>(
+        bufs: &'b mut [IoSliceMut<'a>],
+        f: impl FnOnce(&mut [IoSliceMutCore<'b>]) -> R,
+    ) -> R {
+        with_converted(
+            bufs.iter_mut().map(|buf| IoSliceMutCore::new(buf)),
+            || IoSliceMutCore::new(&mut []),
+            f,
+        )
+    }
+
+    /// Calls `f` with std slices reborrowing the slices `bufs`.
+    pub fn as_std_slices<'b, R>(
+        bufs: &'b mut [Self],
+        f: impl FnOnce(&mut [IoSliceMut<'b>]) -> R,
+    ) -> R {
+        with_converted(
+            bufs.iter_mut().map(|buf| IoSliceMut::new(buf)),
+            || IoSliceMut::new(&mut []),
+            f,
+        )
+    }
+}
+
+impl From<SeekFrom> for io::SeekFrom {
+    fn from(pos: SeekFrom) -> Self {
+        match pos {
+            SeekFrom::Start(n) => io::SeekFrom::Start(n),
+            SeekFrom::End(n) => io::SeekFrom::End(n),
+            SeekFrom::Current(n) => io::SeekFrom::Current(n),
+        }
+    }
+}
+
+impl From<io::SeekFrom> for SeekFrom {
+    fn from(pos: io::SeekFrom) -> Self {
+        match pos {
+            io::SeekFrom::Start(n) => SeekFrom::Start(n),
+            io::SeekFrom::End(n) => SeekFrom::End(n),
+            io::SeekFrom::Current(n) => SeekFrom::Current(n),
+        }
+    }
+}
+
+impl From<CoreErrorKind> for ErrorKind {
+    fn from(kind: CoreErrorKind) -> Self {
+        match kind {
+            CoreErrorKind::NotFound => ErrorKind::NotFound,
+            CoreErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+            CoreErrorKind::ConnectionRefused => ErrorKind::ConnectionRefused,
+            CoreErrorKind::ConnectionReset => ErrorKind::ConnectionReset,
+            CoreErrorKind::ConnectionAborted => ErrorKind::ConnectionAborted,
+            CoreErrorKind::NotConnected => ErrorKind::NotConnected,
+            CoreErrorKind::AddrInUse => ErrorKind::AddrInUse,
+            CoreErrorKind::AddrNotAvailable => ErrorKind::AddrNotAvailable,
+            CoreErrorKind::BrokenPipe => ErrorKind::BrokenPipe,
+            CoreErrorKind::AlreadyExists => ErrorKind::AlreadyExists,
+            CoreErrorKind::WouldBlock => ErrorKind::WouldBlock,
+            CoreErrorKind::InvalidInput => ErrorKind::InvalidInput,
+            CoreErrorKind::InvalidData => ErrorKind::InvalidData,
+            CoreErrorKind::TimedOut => ErrorKind::TimedOut,
+            CoreErrorKind::WriteZero => ErrorKind::WriteZero,
+            CoreErrorKind::Interrupted => ErrorKind::Interrupted,
+            CoreErrorKind::Unsupported => ErrorKind::Unsupported,
+            CoreErrorKind::UnexpectedEof => ErrorKind::UnexpectedEof,
+            CoreErrorKind::OutOfMemory => ErrorKind::OutOfMemory,
+            CoreErrorKind::Other => ErrorKind::Other,
+        }
+    }
+}
+
+impl From<ErrorKind> for CoreErrorKind {
+    fn from(kind: ErrorKind) -> Self {
+        match kind {
+            ErrorKind::NotFound => CoreErrorKind::NotFound,
+            ErrorKind::PermissionDenied => CoreErrorKind::PermissionDenied,
+            ErrorKind::ConnectionRefused => CoreErrorKind::ConnectionRefused,
+            ErrorKind::ConnectionReset => CoreErrorKind::ConnectionReset,
+            ErrorKind::ConnectionAborted => CoreErrorKind::ConnectionAborted,
+            ErrorKind::NotConnected => CoreErrorKind::NotConnected,
+            ErrorKind::AddrInUse => CoreErrorKind::AddrInUse,
+            ErrorKind::AddrNotAvailable => CoreErrorKind::AddrNotAvailable,
+            ErrorKind::BrokenPipe => CoreErrorKind::BrokenPipe,
+            ErrorKind::AlreadyExists => CoreErrorKind::AlreadyExists,
+            ErrorKind::WouldBlock => CoreErrorKind::WouldBlock,
+            ErrorKind::InvalidInput => CoreErrorKind::InvalidInput,
+            ErrorKind::InvalidData => CoreErrorKind::InvalidData,
+            ErrorKind::TimedOut => CoreErrorKind::TimedOut,
+            ErrorKind::WriteZero => CoreErrorKind::WriteZero,
+            ErrorKind::Interrupted => CoreErrorKind::Interrupted,
+            ErrorKind::Unsupported => CoreErrorKind::Unsupported,
+            ErrorKind::UnexpectedEof => CoreErrorKind::UnexpectedEof,
+            ErrorKind::OutOfMemory => CoreErrorKind::OutOfMemory,
+            ErrorKind::Other => CoreErrorKind::Other,
+            _ => CoreErrorKind::Other,
+        }
+    }
+}
+
+impl ClassifyError for Error {
+    fn kind(&self) -> CoreErrorKind {
+        Error::kind(self).into()
+    }
+}
+
+impl<T: ?Sized> SeekCore for T
 where
-    T: Write,
+    T: Seek,
 {
     type Err = Error;
 
-    default fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
-        match Write::write(self, buf) {
-            Ok(0) => Ok(OpRes::Eof),
-            Ok(n) if n == buf.len() => unsafe {
-                Ok(OpRes::Completly(NonZeroUsize::new_unchecked(n)))
-            },
-            Ok(n) => unsafe { Ok(OpRes::Partial(NonZeroUsize::new_unchecked(n))) },
-            Err(e) if e.kind() == ErrorKind::Interrupted => Ok(OpRes::Retry),
-            Err(e) => Err(e),
+    default_fn! {
+        fn seek(&mut self, pos: SeekFrom) -> Result<u64, Self::Err> {
+            Seek::seek(self, pos.into())
         }
     }
 
-    default fn flush(&mut self) -> Result<(), Self::Err> {
-        Write::flush(self)
+    default_fn! {
+        fn rewind(&mut self) -> Result<(), Self::Err> {
+            Seek::rewind(self)
+        }
     }
 
-    default fn write_all(&mut self, buf: &[u8]) -> Result<(), Self::Err> {
-        Write::write_all(self, buf)
+    default_fn! {
+        fn stream_len(&mut self) -> Result<u64, Self::Err> {
+            Seek::stream_len(self)
+        }
     }
 
-    default fn write_fmt(&mut self, fmt: fmt::Arguments) -> Result<(), Self::Err> {
-        Write::write_fmt(self, fmt)
+    default_fn! {
+        fn stream_position(&mut self) -> Result<u64, Self::Err> {
+            Seek::stream_position(self)
+        }
     }
+}
 
-    default fn by_ref(&mut self) -> &mut Self
-    where
-        Self: Sized,
-    {
-        Write::by_ref(self)
+#[cfg(not(feature = "stable"))]
+impl<R: ?Sized, W: ?Sized> CopySpec<W> for R
+where
+    R: Read + ReadCore<Err = Error>,
+    W: Write + WriteCore<Err = Error>,
+{
+    default_fn! {
+        fn copy_to<const N: usize>(
+            &mut self,
+            writer: &mut W,
+        ) -> Result<u64, CopyError<Error, Error>> {
+            let mut buf = [0; N];
+            let mut written = 0;
+            loop {
+                let len = match Read::read(self, &mut buf) {
+                    Ok(0) => return Ok(written),
+                    Ok(len) => len,
+                    Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(CopyError::Read(e)),
+                };
+                Write::write_all(writer, &buf[..len]).map_err(CopyError::Write)?;
+                written += len as u64;
+            }
+        }
     }
 }
 
-impl<T> WriteStd for T
+#[cfg(not(feature = "stable"))]
+impl<R: ?Sized, W: ?Sized> CopySpec<W> for R
 where
-    T: Write + WriteCore<Err = Error>,
+    R: BufRead + ReadCore<Err = Error>,
+    W: Write + WriteCore<Err = Error>,
 {
-    default fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<OpRes, Self::Err> {
-        match Write::write_vectored(self, bufs) {
-            Ok(0) => Ok(OpRes::Eof),
-            Ok(n) => unsafe {
-                let full_len = bufs.iter().map(|b| b.len()).sum();
-                Ok(if n == full_len {
-                    OpRes::Completly(NonZeroUsize::new_unchecked(n))
-                } else {
-                    OpRes::Partial(NonZeroUsize::new_unchecked(n))
-                })
-            },
-            Err(e) if e.kind() == ErrorKind::Interrupted => Ok(OpRes::Retry),
-            Err(e) => Err(e),
+    fn copy_to<const N: usize>(&mut self, writer: &mut W) -> Result<u64, CopyError<Error, Error>> {
+        let mut written = 0;
+        loop {
+            let (len, res) = match BufRead::fill_buf(self) {
+                Ok([]) => return Ok(written),
+                Ok(buf) => write_all_counted(writer, buf),
+                Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(CopyError::Read(e)),
+            };
+            BufRead::consume(self, len);
+            written += len as u64;
+            res.map_err(CopyError::Write)?;
         }
     }
+}
 
-    default fn is_write_vectored(&self) -> bool {
-        Write::is_write_vectored(self)
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        match err.raw_os_error() {
+            Some(code) => Error::from_raw_os_error(code),
+            None => {
+                let kind = err.kind();
+                match err.into_inner() {
+                    Some(inner) => Error::new(kind, inner),
+                    None => Error::from(kind),
+                }
+            }
+        }
     }
+}
 
-    default fn write_all_vectored(&mut self, bufs: &mut [IoSlice<'_>]) -> Result<(), Self::Err> {
-        Write::write_all_vectored(self, bufs)
+impl From<Error> for io::Error {
+    fn from(err: Error) -> Self {
+        match err.raw_os_error() {
+            Some(code) => io::Error::from_raw_os_error(code),
+            None => {
+                let kind = err.kind();
+                match err.into_inner() {
+                    Some(inner) => io::Error::new(kind, inner),
+                    None => io::Error::from(kind),
+                }
+            }
+        }
     }
 }
 
-pub(crate) fn default_write_vectored<F, E>(write: F, bufs: &[IoSlice<'_>]) -> Result<OpRes, E>
+/// Exposes a type implementing `std::io::Read`, `Write`, `Seek` or `BufRead`
+/// through the legacy traits of this crate, which makes it usable as
+/// `ReadCore`, `WriteCore`, `SeekCore` and `BufReadCore`.
+#[derive(Debug, Default)]
+pub struct FromStdIo<T> {
+    inner: T,
+}
+
+impl<T> FromStdIo<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<T: io::Read> Read for FromStdIo<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        Ok(self.inner.read(buf)?)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize, Error> {
+        Ok(self.inner.read_vectored(bufs)?)
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize, Error> {
+        Ok(self.inner.read_to_end(buf)?)
+    }
+
+    fn read_to_string(&mut self, buf: &mut String) -> Result<usize, Error> {
+        Ok(self.inner.read_to_string(buf)?)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Error> {
+        Ok(self.inner.read_exact(buf)?)
+    }
+}
+
+impl<T: io::BufRead> BufRead for FromStdIo<T> {
+    fn fill_buf(&mut self) -> Result<&[u8], Error> {
+        Ok(self.inner.fill_buf()?)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+impl<T: io::Write> Write for FromStdIo<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        Ok(self.inner.write(buf)?)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize, Error> {
+        Ok(self.inner.write_vectored(bufs)?)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        Ok(self.inner.flush()?)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Error> {
+        Ok(self.inner.write_all(buf)?)
+    }
+
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<(), Error> {
+        Ok(self.inner.write_fmt(fmt)?)
+    }
+}
+
+impl<T: io::Seek> Seek for FromStdIo<T> {
+    fn seek(&mut self, pos: io::SeekFrom) -> Result<u64, Error> {
+        Ok(self.inner.seek(pos)?)
+    }
+
+    fn rewind(&mut self) -> Result<(), Error> {
+        Ok(self.inner.rewind()?)
+    }
+
+    fn stream_position(&mut self) -> Result<u64, Error> {
+        Ok(self.inner.stream_position()?)
+    }
+}
+
+/// Exposes a `ReadCore`, `WriteCore`, `SeekCore` or `BufReadCore` as
+/// `std::io::Read`, `Write`, `Seek` or `BufRead`. `OpRes::Retry` and
+/// `OpRes::WouldBlock` are reported as errors of kind `Interrupted` and
+/// `WouldBlock`, other errors keep the kind reported by `ClassifyError`.
+#[derive(Debug, Default)]
+pub struct IntoStdIo<T> {
+    inner: T,
+}
+
+impl<T> IntoStdIo<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+#[derive(Debug)]
+struct CoreIoError<E>(E);
+
+impl<E: fmt::Debug> fmt::Display for CoreIoError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl<E: fmt::Debug> std::error::Error for CoreIoError<E> {}
+
+/// Converts a core error into an `io::Error`. An `Error` of the legacy layer
+/// is converted directly, so that OS error codes are preserved.
+fn into_io_error<E>(err: E) -> io::Error
+where
+    E: ClassifyError + fmt::Debug + Send + Sync + 'static,
+{
+    let mut err = Some(err);
+    if let Some(err) = (&mut err as &mut dyn Any).downcast_mut::<Option<Error>>() {
+        return err.take().unwrap().into();
+    }
+    let err = err.unwrap();
+    io::Error::new(err.kind().into(), CoreIoError(err))
+}
+
+fn op_res_to_io(res: OpRes) -> io::Result<usize> {
+    match res {
+        OpRes::Retry => Err(ErrorKind::Interrupted.into()),
+        OpRes::WouldBlock => Err(ErrorKind::WouldBlock.into()),
+        OpRes::Eof | OpRes::Empty => Ok(0),
+        OpRes::Partial(n) | OpRes::Completly(n) => Ok(n.get()),
+    }
+}
+
+impl<T> io::Read for IntoStdIo<T>
+where
+    T: ReadCore,
+    T::Err: ClassifyError + fmt::Debug + Send + Sync + 'static,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let res = self.inner.read(buf).map_err(into_io_error)?;
+        op_res_to_io(res)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        let res = IoSliceMutCore::from_std_slices(bufs, |bufs| self.inner.read_vectored(bufs));
+        let res = res.map_err(into_io_error)?;
+        op_res_to_io(res)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.inner.read_exact(buf).map_err(into_io_error)
+    }
+}
+
+impl<T> io::BufRead for IntoStdIo<T>
+where
+    T: BufReadCore,
+    T::Err: ClassifyError + fmt::Debug + Send + Sync + 'static,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        match self.inner.fill_buf().map_err(into_io_error)? {
+            FillRes::Data(buf) => Ok(buf),
+            FillRes::Eof => Ok(&[]),
+            FillRes::Retry => Err(ErrorKind::Interrupted.into()),
+            FillRes::WouldBlock => Err(ErrorKind::WouldBlock.into()),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt)
+    }
+}
+
+impl<T> io::Write for IntoStdIo<T>
+where
+    T: WriteCore,
+    T::Err: ClassifyError + fmt::Debug + Send + Sync + 'static,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let res = self.inner.write(buf).map_err(into_io_error)?;
+        op_res_to_io(res)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        let res = IoSliceCore::from_std_slices(bufs, |bufs| self.inner.write_vectored(bufs));
+        let res = res.map_err(into_io_error)?;
+        op_res_to_io(res)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush().map_err(into_io_error)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.inner.write_all(buf).map_err(into_io_error)
+    }
+}
+
+impl<T> io::Seek for IntoStdIo<T>
 where
-    F: FnOnce(&[u8]) -> Result<OpRes, E>,
+    T: SeekCore,
+    T::Err: ClassifyError + fmt::Debug + Send + Sync + 'static,
 {
-    let buf = bufs
-        .iter()
-        .find(|b| !b.is_empty())
-        .map_or(&[][..], |b| &**b);
-    write(buf)
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos.into()).map_err(into_io_error)
+    }
 }
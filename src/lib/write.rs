@@ -33,7 +33,7 @@ pub trait Write {
     }
 
     fn write_all_vectored(&mut self, mut bufs: &mut [IoSlice<'_>]) -> Result<(), Error> {
-        bufs = IoSlice::advance(bufs, 0);
+        IoSlice::advance_slices(&mut bufs, 0);
         while !bufs.is_empty() {
             match self.write_vectored(bufs) {
                 Ok(0) => {
@@ -42,7 +42,7 @@ pub trait Write {
                         "failed to write whole buffer",
                     ));
                 }
-                Ok(n) => bufs = IoSlice::advance(bufs, n),
+                Ok(n) => IoSlice::advance_slices(&mut bufs, n),
                 Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
                 Err(e) => return Err(e),
             }
@@ -0,0 +1,81 @@
+use rw_poc::{BitOrder, BitReader, BitWriter, Cursor, CursorError, ReadCore};
+
+#[test]
+fn msb_first() {
+    let mut writer = BitWriter::new(Cursor::new(Vec::new()), BitOrder::MsbFirst);
+    writer.write_bits(0b101, 3).unwrap();
+    writer.write_bool(true).unwrap();
+    writer.write_bits(0xABC, 12).unwrap();
+    writer.write_bits(0b11, 2).unwrap();
+    writer.flush().unwrap();
+    let data = writer.into_inner().into_inner();
+    assert_eq!(data, [0b1011_1010, 0b1011_1100, 0b1100_0000]);
+
+    let mut reader = BitReader::new(Cursor::new(&data[..]), BitOrder::MsbFirst);
+    assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+    assert!(reader.read_bool().unwrap());
+    assert_eq!(reader.read_bits(12).unwrap(), 0xABC);
+    assert_eq!(reader.read_bits(2).unwrap(), 0b11);
+    reader.align_to_byte();
+    assert_eq!(reader.read_bits(1), Err(CursorError::UnexpectedEndOfFile));
+}
+
+#[test]
+fn lsb_first() {
+    let mut writer = BitWriter::new(Cursor::new(Vec::new()), BitOrder::LsbFirst);
+    writer.write_bits(0b101, 3).unwrap();
+    writer.write_bool(true).unwrap();
+    writer.write_bits(0xABC, 12).unwrap();
+    writer.write_bits(0b11, 2).unwrap();
+    writer.flush().unwrap();
+    let data = writer.into_inner().into_inner();
+    assert_eq!(data, [0b1100_1101, 0b1010_1011, 0b0000_0011]);
+
+    let mut reader = BitReader::new(Cursor::new(&data[..]), BitOrder::LsbFirst);
+    assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+    assert!(reader.read_bool().unwrap());
+    assert_eq!(reader.read_bits(12).unwrap(), 0xABC);
+    assert_eq!(reader.read_bits(2).unwrap(), 0b11);
+}
+
+#[test]
+fn wide_fields_round_trip() {
+    for order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+        let mut writer = BitWriter::new(Cursor::new(Vec::new()), order);
+        for n in 0..=64 {
+            writer.write_bits(u64::MAX, n).unwrap();
+            writer.write_bits(0x0123_4567_89AB_CDEF, 64).unwrap();
+        }
+        writer.flush().unwrap();
+        let data = writer.into_inner().into_inner();
+
+        let mut reader = BitReader::new(Cursor::new(&data[..]), order);
+        for n in 0..=64 {
+            let expected = if n == 64 { u64::MAX } else { (1 << n) - 1 };
+            assert_eq!(reader.read_bits(n).unwrap(), expected);
+            assert_eq!(reader.read_bits(64).unwrap(), 0x0123_4567_89AB_CDEF);
+        }
+    }
+}
+
+#[test]
+fn align_leaves_remaining_bytes_to_inner() {
+    let data = [0xF0, 0x12, 0x34];
+    let mut reader = BitReader::new(Cursor::new(&data[..]), BitOrder::MsbFirst);
+    assert_eq!(reader.read_bits(4).unwrap(), 0xF);
+    reader.align_to_byte();
+    let mut rest = [0; 2];
+    reader.get_mut().read_exact(&mut rest).unwrap();
+    assert_eq!(rest, [0x12, 0x34]);
+}
+
+#[test]
+fn truncated_field() {
+    let data = [0xFF];
+    let mut reader = BitReader::new(Cursor::new(&data[..]), BitOrder::LsbFirst);
+    assert_eq!(reader.read_bits(12), Err(CursorError::UnexpectedEndOfFile));
+
+    let mut writer = BitWriter::new(Cursor::new([0; 1]), BitOrder::MsbFirst);
+    writer.write_bits(0xFFF, 12).unwrap();
+    assert_eq!(writer.flush(), Err(CursorError::UnexpectedEndOfFile));
+}
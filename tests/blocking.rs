@@ -0,0 +1,46 @@
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use rw_poc::{AsyncReadCore, Blocking, CursorError, OpRes, ReadCore};
+
+/// Alternates between `Poll::Pending` and handing out a single byte.
+#[derive(Default)]
+struct Flaky {
+    ready: bool,
+}
+
+impl AsyncReadCore for Flaky {
+    type Err = CursorError;
+
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<OpRes, Self::Err>> {
+        self.ready = !self.ready;
+        if !self.ready {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        buf[0] = b'x';
+        Poll::Ready(Ok(OpRes::Completly(NonZeroUsize::MIN)))
+    }
+}
+
+#[test]
+fn pending_is_would_block() {
+    let mut reader = Blocking::new(Flaky::default());
+    let mut buf = [0; 1];
+    assert!(matches!(
+        reader.read(&mut buf).unwrap(),
+        OpRes::Completly(_)
+    ));
+    assert!(matches!(reader.read(&mut buf).unwrap(), OpRes::WouldBlock));
+
+    let mut buf = [0; 4];
+    for chunk in buf.chunks_mut(1) {
+        reader.read_exact(chunk).unwrap();
+    }
+    assert_eq!(&buf, b"xxxx");
+}
@@ -0,0 +1,213 @@
+use std::num::NonZeroUsize;
+
+use rw_poc::{
+    Broadcast, BroadcastArray, BroadcastArrayError, BroadcastError, BroadcastPolicy, Cursor,
+    CursorError, OpRes, WriteCore,
+};
+
+const EOF: CursorError = CursorError::UnexpectedEndOfFile;
+
+/// Accepts `room` bytes, then fails once and accepts everything afterwards.
+struct FailOnce {
+    data: Vec<u8>,
+    room: usize,
+    failed: bool,
+}
+
+impl FailOnce {
+    fn new(room: usize) -> Self {
+        Self {
+            data: Vec::new(),
+            room,
+            failed: false,
+        }
+    }
+}
+
+impl WriteCore for FailOnce {
+    type Err = CursorError;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        let n = if self.failed {
+            buf.len()
+        } else if self.data.len() == self.room {
+            self.failed = true;
+            return Err(CursorError::OutOfBounds);
+        } else {
+            buf.len().min(self.room - self.data.len())
+        };
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(match NonZeroUsize::new(n) {
+            Some(n) if n.get() == buf.len() => OpRes::Completly(n),
+            Some(n) => OpRes::Partial(n),
+            None => OpRes::Empty,
+        })
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
+#[test]
+fn empty_buffers() {
+    let mut broadcast = Broadcast::new(Cursor::new(Vec::new()), Cursor::new([0; 2]));
+    assert!(matches!(broadcast.write(&[]), Ok(OpRes::Empty)));
+
+    let mut broadcast = BroadcastArray::new([Cursor::new([0; 2]), Cursor::new([0; 2])]);
+    assert!(matches!(broadcast.write(&[]), Ok(OpRes::Empty)));
+}
+
+#[test]
+fn propagate_reports_common_part() {
+    let mut broadcast = Broadcast::new(Cursor::new(Vec::new()), FailOnce::new(3));
+    assert!(matches!(broadcast.write(b"abcdef"), Ok(OpRes::Partial(n)) if n.get() == 3));
+    assert_eq!(broadcast.get_ref().0.get_ref(), b"abcdef");
+    assert_eq!(broadcast.get_ref().1.data, b"abc");
+    assert_eq!(
+        broadcast.write(b"def").err(),
+        Some(BroadcastError::Second(CursorError::OutOfBounds))
+    );
+
+    assert!(matches!(broadcast.write(b"def"), Ok(OpRes::Completly(_))));
+    broadcast.write_all(b"gh").unwrap();
+    assert_eq!(broadcast.get_ref().0.get_ref(), b"abcdefgh");
+    assert_eq!(broadcast.get_ref().1.data, b"abcdefgh");
+}
+
+#[test]
+fn array_propagate_resumes_lagging_sinks() {
+    let mut broadcast = BroadcastArray::new([FailOnce::new(3), FailOnce::new(5)]);
+    assert_eq!(
+        broadcast.write(b"abcdef").err(),
+        Some(BroadcastArrayError::Sink(0, CursorError::OutOfBounds))
+    );
+    assert!(broadcast.get_ref()[1].data.is_empty());
+    assert!(matches!(broadcast.write(b"abcdef"), Ok(OpRes::Partial(n)) if n.get() == 5));
+    assert_eq!(
+        broadcast.write(b"f").err(),
+        Some(BroadcastArrayError::Sink(1, CursorError::OutOfBounds))
+    );
+    assert!(matches!(broadcast.write(b"f"), Ok(OpRes::Completly(_))));
+    broadcast.write_all(b"gh").unwrap();
+    for sink in broadcast.get_ref() {
+        assert_eq!(sink.data, b"abcdefgh");
+    }
+}
+
+#[test]
+fn shorter_retry_is_rejected() {
+    let mut broadcast = Broadcast::new(Cursor::new(Vec::new()), FailOnce::new(3));
+    assert!(matches!(broadcast.write(b"abcdef"), Ok(OpRes::Partial(_))));
+    assert!(broadcast.write(b"def").is_err());
+    assert_eq!(broadcast.write(b"xy").err(), Some(BroadcastError::Diverged));
+    assert!(matches!(broadcast.write(b"def"), Ok(OpRes::Completly(_))));
+    assert_eq!(broadcast.get_ref().0.get_ref(), b"abcdef");
+    assert_eq!(broadcast.get_ref().1.data, b"abcdef");
+
+    let mut broadcast = BroadcastArray::new([FailOnce::new(3), FailOnce::new(5)]);
+    assert!(broadcast.write(b"abcdef").is_err());
+    assert_eq!(
+        broadcast.write(b"ab").err(),
+        Some(BroadcastArrayError::Diverged)
+    );
+}
+
+/// Reports `Retry` on every other call, starting with the first, and
+/// accepts two bytes otherwise.
+#[derive(Default)]
+struct Stutter {
+    data: Vec<u8>,
+    ready: bool,
+}
+
+impl WriteCore for Stutter {
+    type Err = CursorError;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        self.ready = !self.ready;
+        if self.ready {
+            return Ok(OpRes::Retry);
+        }
+        let n = buf.len().min(2);
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(OpRes::Partial(NonZeroUsize::new(n).unwrap()))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
+#[test]
+fn retry_of_sink_is_passed_on() {
+    let mut broadcast = Broadcast::new(Cursor::new(Vec::new()), Stutter::default());
+    assert!(matches!(broadcast.write(b"abcdef"), Ok(OpRes::Retry)));
+    assert_eq!(broadcast.get_ref().0.get_ref(), b"abcdef");
+    assert!(matches!(broadcast.write(b"abcdef"), Ok(OpRes::Partial(n)) if n.get() == 2));
+    broadcast.write_all(b"cdef").unwrap();
+    assert_eq!(broadcast.get_ref().0.get_ref(), b"abcdef");
+    assert_eq!(broadcast.get_ref().1.data, b"abcdef");
+}
+
+#[test]
+fn detach_keeps_writing_to_other_sink() {
+    let mut broadcast = Broadcast::new(Cursor::new([0; 2]), Cursor::new(Vec::new()));
+    broadcast.set_policy(BroadcastPolicy::Detach);
+    broadcast.write_all(b"abc").unwrap();
+    broadcast.write_all(b"d").unwrap();
+    broadcast.flush().unwrap();
+    assert_eq!(broadcast.get_ref().1.get_ref(), b"abcd");
+    assert_eq!(broadcast.take_errors(), (Some(EOF), None));
+    assert_eq!(broadcast.take_errors(), (None, None));
+}
+
+#[test]
+fn detach_keeps_sinks_detached_until_taken() {
+    let mut broadcast = Broadcast::new(Cursor::new([0; 2]), Cursor::new([0; 2]));
+    broadcast.set_policy(BroadcastPolicy::Detach);
+    assert_eq!(
+        broadcast.write(b"abc").err(),
+        Some(BroadcastError::Both(EOF, EOF))
+    );
+    assert!(matches!(broadcast.write(b"d"), Ok(OpRes::Eof)));
+    broadcast.flush().unwrap();
+
+    assert_eq!(broadcast.take_errors(), (None, None));
+    broadcast.get_mut().0.set_position(0);
+    broadcast.get_mut().1.set_position(0);
+    assert!(matches!(broadcast.write(b"x"), Ok(OpRes::Completly(_))));
+    assert_eq!(broadcast.get_ref().0.get_ref(), b"xb");
+    assert_eq!(broadcast.get_ref().1.get_ref(), b"xb");
+}
+
+#[test]
+fn array_detach_keeps_sinks_detached_until_taken() {
+    let mut broadcast = BroadcastArray::new([Cursor::new([0; 3]), Cursor::new([0; 3])]);
+    broadcast.set_policy(BroadcastPolicy::Detach);
+    assert!(matches!(broadcast.write(b"ab"), Ok(OpRes::Completly(_))));
+    assert_eq!(
+        broadcast.write(b"cd").err(),
+        Some(BroadcastArrayError::All([Some(EOF), Some(EOF)]))
+    );
+    assert!(matches!(broadcast.write(b"e"), Ok(OpRes::Eof)));
+    broadcast.flush().unwrap();
+
+    assert_eq!(broadcast.take_error(0), None);
+    assert_eq!(
+        broadcast.write(b"e").err(),
+        Some(BroadcastArrayError::All([Some(EOF), None]))
+    );
+    assert_eq!(broadcast.take_error(0), None);
+    broadcast.get_mut()[0].set_position(0);
+    assert!(matches!(broadcast.write(b"x"), Ok(OpRes::Completly(_))));
+    assert_eq!(broadcast.get_ref()[0].get_ref(), b"xbc");
+    assert_eq!(broadcast.get_ref()[1].get_ref(), b"abc");
+}
+
+#[test]
+fn array_without_sinks() {
+    let mut broadcast: BroadcastArray<Cursor<[u8; 1]>, 0> = BroadcastArray::new([]);
+    broadcast.write_all(b"abc").unwrap();
+    broadcast.flush().unwrap();
+}
@@ -0,0 +1,129 @@
+use std::cell::Cell;
+
+use rw_poc::{
+    BufReadCore, BufReaderCore, BufWriterCore, Cursor, CursorError, DropPolicy, FillRes, OpRes,
+    ReadCore, WriteCore,
+};
+
+/// Counts the calls to `read` or `write` on the wrapped type.
+struct Counting<T> {
+    inner: T,
+    calls: usize,
+}
+
+impl<R: ReadCore> ReadCore for Counting<R> {
+    type Err = R::Err;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
+        self.calls += 1;
+        self.inner.read(buf)
+    }
+}
+
+impl<W: WriteCore> WriteCore for Counting<W> {
+    type Err = W::Err;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        self.calls += 1;
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        self.inner.flush()
+    }
+}
+
+#[test]
+fn empty_read_does_not_touch_reader() {
+    let inner = Counting {
+        inner: Cursor::new(&b"abc"[..]),
+        calls: 0,
+    };
+    let mut reader = BufReaderCore::<_, 2>::new(inner);
+    assert!(matches!(reader.read(&mut []), Ok(OpRes::Empty)));
+    assert_eq!(reader.get_ref().calls, 0);
+
+    assert!(matches!(reader.fill_buf(), Ok(FillRes::Data(b"ab"))));
+    assert!(matches!(reader.read(&mut []), Ok(OpRes::Empty)));
+    assert_eq!(reader.get_ref().calls, 1);
+
+    let mut buf = [0; 3];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"abc");
+}
+
+#[test]
+fn empty_write_does_not_touch_writer() {
+    let inner = Counting {
+        inner: Cursor::new([0; 4]),
+        calls: 0,
+    };
+    let mut writer = BufWriterCore::<_, 2>::new(inner);
+    assert!(matches!(writer.write(&[]), Ok(OpRes::Empty)));
+    writer.write_all(b"a").unwrap();
+    assert!(matches!(writer.write(&[]), Ok(OpRes::Empty)));
+    assert_eq!(writer.buffer(), b"a");
+    assert_eq!(writer.get_ref().calls, 0);
+
+    let inner = writer.into_inner().ok().unwrap();
+    assert_eq!(inner.calls, 1);
+    assert_eq!(&inner.inner.into_inner()[..1], b"a");
+}
+
+#[test]
+fn write_fmt_is_written_once() {
+    let inner = Counting {
+        inner: Cursor::new(Vec::new()),
+        calls: 0,
+    };
+    let mut writer = BufWriterCore::<_, 16>::new(inner);
+    let name = "ab";
+    write!(writer, "{}-{}", 12, name).unwrap();
+    assert_eq!(writer.buffer(), b"12-ab");
+    assert_eq!(writer.get_ref().calls, 0);
+
+    let inner = writer.into_inner().ok().unwrap();
+    assert_eq!(inner.calls, 1);
+    assert_eq!(inner.inner.into_inner(), b"12-ab");
+}
+
+#[test]
+fn panic_in_debug_while_unwinding() {
+    let res = std::panic::catch_unwind(|| {
+        let mut writer = BufWriterCore::<_, 4>::new(Cursor::new([0; 4]));
+        writer.set_drop_policy(DropPolicy::PanicInDebug);
+        writer.write_all(b"a").unwrap();
+        panic!("first panic");
+    });
+    let payload = res.unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"first panic"));
+}
+
+/// Fails every write and counts the attempts.
+struct Failing<'a>(&'a Cell<usize>);
+
+impl WriteCore for Failing<'_> {
+    type Err = CursorError;
+
+    fn write(&mut self, _buf: &[u8]) -> Result<OpRes, Self::Err> {
+        self.0.set(self.0.get() + 1);
+        Err(CursorError::OutOfBounds)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
+#[test]
+fn into_inner_error_does_not_flush_on_drop() {
+    let calls = Cell::new(0);
+    let mut writer = BufWriterCore::<_, 4>::new(Failing(&calls));
+    writer.write_all(b"ab").unwrap();
+    let err = writer.into_inner().err().unwrap();
+    assert_eq!(err.error(), &CursorError::OutOfBounds);
+    assert_eq!(calls.get(), 1);
+
+    drop(err);
+    assert_eq!(calls.get(), 1);
+}
@@ -0,0 +1,105 @@
+use std::num::NonZeroUsize;
+
+use rw_poc::{copy, Copier, CopyError, Cursor, CursorError, OpRes, ReadCore, WriteCore};
+
+const DATA: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+/// Reports `WouldBlock` on every other call and hands out data otherwise.
+struct FlakyReader {
+    inner: Cursor<&'static [u8]>,
+    ready: bool,
+}
+
+impl ReadCore for FlakyReader {
+    type Err = CursorError;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<OpRes, Self::Err> {
+        self.ready = !self.ready;
+        if !self.ready {
+            return Ok(OpRes::WouldBlock);
+        }
+        self.inner.read(buf)
+    }
+}
+
+/// Reports `WouldBlock` on every other call and accepts at most three bytes
+/// otherwise.
+#[derive(Default)]
+struct ChoppyWriter {
+    data: Vec<u8>,
+    ready: bool,
+}
+
+impl WriteCore for ChoppyWriter {
+    type Err = CursorError;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        self.ready = !self.ready;
+        if !self.ready {
+            return Ok(OpRes::WouldBlock);
+        }
+        let n = buf.len().min(3);
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(match NonZeroUsize::new(n) {
+            Some(n) if n.get() == buf.len() => OpRes::Completly(n),
+            Some(n) => OpRes::Partial(n),
+            None => OpRes::Empty,
+        })
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
+#[test]
+fn copies_everything() {
+    let mut reader = Cursor::new(DATA);
+    let mut writer = Cursor::new(Vec::new());
+    assert_eq!(copy(&mut reader, &mut writer), Ok(DATA.len() as u64));
+    assert_eq!(writer.get_ref().as_slice(), DATA);
+}
+
+#[test]
+fn copier_resumes_after_would_block() {
+    let mut reader = FlakyReader {
+        inner: Cursor::new(DATA),
+        ready: false,
+    };
+    let mut writer = ChoppyWriter::default();
+    let mut copier = Copier::<8>::new();
+    let mut read_blocked = 0;
+    let mut write_blocked = 0;
+    let copied = loop {
+        match copier.copy(&mut reader, &mut writer) {
+            Ok(n) => break n,
+            Err(CopyError::Read(CursorError::WouldBlock)) => read_blocked += 1,
+            Err(CopyError::Write(CursorError::WouldBlock)) => write_blocked += 1,
+            Err(err) => panic!("unexpected error {:?}", err),
+        }
+    };
+    assert_eq!(copied, DATA.len() as u64);
+    assert_eq!(copier.written(), DATA.len() as u64);
+    assert!(copier.pending().is_empty());
+    assert_eq!(writer.data, DATA);
+    assert!(read_blocked > 0);
+    assert!(write_blocked > 0);
+}
+
+#[test]
+fn copier_keeps_unwritten_data() {
+    let mut reader = Cursor::new(DATA);
+    let mut writer = Cursor::new([0; 5]);
+    let mut copier = Copier::<8>::new();
+    assert_eq!(
+        copier.copy(&mut reader, &mut writer),
+        Err(CopyError::Write(CursorError::UnexpectedEndOfFile))
+    );
+    assert_eq!(copier.written(), 5);
+    assert_eq!(copier.pending(), &DATA[5..8]);
+    assert_eq!(reader.position(), 8);
+
+    let mut writer = Cursor::new(Vec::new());
+    assert_eq!(copier.copy(&mut reader, &mut writer), Ok(DATA.len() as u64));
+    assert_eq!(writer.get_ref().as_slice(), &DATA[5..]);
+}
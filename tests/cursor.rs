@@ -0,0 +1,32 @@
+use rw_poc::{Cursor, OpRes, ReadCore, WriteCore};
+
+#[test]
+fn empty_buffers() {
+    let mut reader = Cursor::new(&b"abc"[..]);
+    assert!(matches!(reader.read(&mut []), Ok(OpRes::Empty)));
+    reader.set_position(3);
+    assert!(matches!(reader.read(&mut []), Ok(OpRes::Empty)));
+    assert!(matches!(reader.read(&mut [0]), Ok(OpRes::Eof)));
+
+    let mut writer = Cursor::new([0; 2]);
+    assert!(matches!(writer.write(&[]), Ok(OpRes::Empty)));
+    writer.write_all(b"ab").unwrap();
+    assert!(matches!(writer.write(&[]), Ok(OpRes::Empty)));
+    assert!(matches!(writer.write(b"c"), Ok(OpRes::Eof)));
+
+    let mut writer = Cursor::new(Vec::new());
+    writer.set_position(4);
+    assert!(matches!(writer.write(&[]), Ok(OpRes::Empty)));
+    assert!(writer.get_ref().is_empty());
+}
+
+#[cfg(target_pointer_width = "32")]
+#[test]
+fn vec_position_beyond_usize() {
+    use rw_poc::CursorError;
+
+    let mut writer = Cursor::new(Vec::new());
+    writer.set_position(u64::from(u32::MAX) + 1);
+    assert_eq!(writer.write(b"a").err(), Some(CursorError::OutOfBounds));
+    assert!(writer.get_ref().is_empty());
+}
@@ -0,0 +1,43 @@
+#!/usr/bin/env python3
+"""Generates the inflate test corpus with the zlib and gzip modules of the
+Python standard library. Run from this directory."""
+
+import gzip
+import random
+import zlib
+
+rng = random.Random(1)
+words = [b"read", b"write", b"seek", b"core", b"buffer", b"cursor", b"window", b"inflate"]
+text = b""
+while len(text) < 48 * 1024:
+    line = b" ".join(rng.choice(words) for _ in range(rng.randint(1, 12)))
+    text += line + b"\n"
+noise = bytes(rng.getrandbits(8) for _ in range(40 * 1024))
+
+
+def deflate(data, level, wbits):
+    compressor = zlib.compressobj(level, zlib.DEFLATED, wbits)
+    return compressor.compress(data) + compressor.flush()
+
+
+def gzip_file(data, name):
+    with open(name, "wb") as file:
+        with gzip.GzipFile(filename="text.txt", mode="wb", fileobj=file, mtime=0) as gz:
+            gz.write(data)
+
+
+with open("text.txt", "wb") as file:
+    file.write(text)
+with open("noise.bin", "wb") as file:
+    file.write(noise)
+with open("text.deflate", "wb") as file:
+    file.write(deflate(text, 9, -15))
+with open("text.zz", "wb") as file:
+    file.write(deflate(text, 9, 15))
+with open("stored.zz", "wb") as file:
+    file.write(deflate(text, 0, 15))
+with open("fixed.zz", "wb") as file:
+    file.write(deflate(b"hello hello hello hello\n", 9, 15))
+gzip_file(text, "text.gz")
+gzip_file(noise, "noise.gz")
+gzip_file(b"", "empty.gz")
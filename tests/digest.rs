@@ -0,0 +1,103 @@
+use rw_poc::{
+    Adler32, Crc16, Crc32, Cursor, Digest, HashingReader, HashingWriter, OpRes, ReadCore, Sha256,
+    WriteCore, Xxh32,
+};
+
+const CHECK: &[u8] = b"123456789";
+
+fn digest<D: Digest + Default>(data: &[u8]) -> D::Output {
+    let mut digest = D::default();
+    digest.update(data);
+    digest.finish()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn crc16_known_answers() {
+    assert_eq!(digest::<Crc16>(b""), 0xFFFF);
+    assert_eq!(digest::<Crc16>(CHECK), 0x29B1);
+}
+
+#[test]
+fn crc32_known_answers() {
+    assert_eq!(digest::<Crc32>(b""), 0);
+    assert_eq!(digest::<Crc32>(CHECK), 0xCBF4_3926);
+    assert_eq!(
+        digest::<Crc32>(b"The quick brown fox jumps over the lazy dog"),
+        0x414F_A339
+    );
+}
+
+#[test]
+fn adler32_known_answers() {
+    assert_eq!(digest::<Adler32>(b""), 1);
+    assert_eq!(digest::<Adler32>(CHECK), 0x091E_01DE);
+    assert_eq!(digest::<Adler32>(b"Wikipedia"), 0x11E6_0398);
+    assert_eq!(digest::<Adler32>(&[0xFF; 100_000]), 0x149A_302C);
+}
+
+#[test]
+fn sha256_known_answers() {
+    assert_eq!(
+        hex(&digest::<Sha256>(b"")),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        hex(&digest::<Sha256>(b"abc")),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        hex(&digest::<Sha256>(
+            b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+        )),
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+    );
+    assert_eq!(
+        hex(&digest::<Sha256>(&[b'a'; 1_000_000])),
+        "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+    );
+}
+
+#[test]
+fn incremental_update_matches_one_shot() {
+    let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+    for split in [0, 1, 55, 56, 63, 64, 65, 999] {
+        let mut sha = Sha256::new();
+        sha.update(&data[..split]);
+        sha.update(&data[split..]);
+        assert_eq!(sha.finish(), digest::<Sha256>(&data));
+    }
+}
+
+#[test]
+fn hashing_reader_follows_read_counts() {
+    let mut reader = HashingReader::new(Cursor::new(CHECK).take(5), Crc32::new());
+    let mut buf = [0; 8];
+    assert!(matches!(reader.read(&mut buf), Ok(OpRes::Partial(_))));
+    assert!(matches!(reader.read(&mut buf), Ok(OpRes::Eof)));
+    assert_eq!(reader.finish(), digest::<Crc32>(&CHECK[..5]));
+}
+
+#[test]
+fn hashing_writer_follows_write_counts() {
+    let mut writer = HashingWriter::new(Cursor::new([0; 4]), Crc16::new());
+    assert!(matches!(writer.write(CHECK), Ok(OpRes::Partial(_))));
+    assert!(writer.write_all(CHECK).is_err());
+    assert_eq!(writer.finish(), digest::<Crc16>(&CHECK[..4]));
+}
+
+#[test]
+fn xxh32_known_answers() {
+    assert_eq!(digest::<Xxh32>(b""), 0x02CC_5D05);
+    assert_eq!(digest::<Xxh32>(b"abc"), 0x32D1_53FF);
+    assert_eq!(
+        digest::<Xxh32>(b"Nobody inspects the spammish repetition"),
+        0xE229_3B2F
+    );
+    let mut seeded = Xxh32::with_seed(0x9E37_79B1);
+    seeded.update(b"");
+    assert_eq!(seeded.finish(), 0x36B7_8AE7);
+}
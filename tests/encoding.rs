@@ -0,0 +1,100 @@
+use rw_poc::{
+    Base64Decoder, Base64Encoder, Cursor, CursorError, HexDecoder, HexEncoder, OpRes, ReadAlloc,
+    ReadCore, WriteCore,
+};
+
+// Test vectors of RFC 4648
+const VECTORS: &[(&[u8], &str)] = &[
+    (b"", ""),
+    (b"f", "Zg=="),
+    (b"fo", "Zm8="),
+    (b"foo", "Zm9v"),
+    (b"foob", "Zm9vYg=="),
+    (b"fooba", "Zm9vYmE="),
+    (b"foobar", "Zm9vYmFy"),
+];
+
+fn base64(data: &[u8]) -> String {
+    let mut encoder = Base64Encoder::new(Cursor::new(Vec::new()));
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap();
+    String::from_utf8(encoder.into_inner().into_inner()).unwrap()
+}
+
+fn decode<R: ReadCore<Err = CursorError>>(mut reader: R) -> Result<Vec<u8>, CursorError> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).map(|_| data)
+}
+
+#[test]
+fn base64_roundtrip() {
+    for (data, encoded) in VECTORS {
+        assert_eq!(base64(data), *encoded);
+        let decoded = decode(Base64Decoder::new(Cursor::new(encoded.as_bytes()))).unwrap();
+        assert_eq!(decoded, *data);
+    }
+    let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+    let decoded = decode(Base64Decoder::new(Cursor::new(base64(&data).as_bytes()))).unwrap();
+    assert_eq!(decoded, data);
+}
+
+#[test]
+fn base64_decoder_rejects_invalid_input() {
+    for input in ["Zg=", "Zg", "Z===", "Zm9v!", "Zg==Zg==", "Zm9v="] {
+        let res = decode(Base64Decoder::new(Cursor::new(input.as_bytes())));
+        assert_eq!(res, Err(CursorError::InvalidEncoding), "{}", input);
+    }
+    let res = decode(Base64Decoder::new(Cursor::new(&b"Zm9v\r\nYmFy\n"[..])));
+    assert_eq!(res.unwrap(), b"foobar");
+}
+
+#[test]
+fn hex_roundtrip() {
+    let mut encoder = HexEncoder::new(Cursor::new(Vec::new()));
+    encoder.write_all(&[0x00, 0x7F, 0xAB, 0xFF]).unwrap();
+    encoder.finish().unwrap();
+    assert_eq!(encoder.get_ref().get_ref(), b"007fabff");
+
+    let decoded = decode(HexDecoder::new(Cursor::new(&b"00 7f\nAB ff"[..]))).unwrap();
+    assert_eq!(decoded, [0x00, 0x7F, 0xAB, 0xFF]);
+    for input in ["0", "0g", "abc"] {
+        let res = decode(HexDecoder::new(Cursor::new(input.as_bytes())));
+        assert_eq!(res, Err(CursorError::InvalidEncoding), "{}", input);
+    }
+}
+
+#[test]
+fn decoders_respect_small_buffers() {
+    let mut decoder = Base64Decoder::new(Cursor::new(&b"Zm9vYmFy"[..]));
+    let mut data = Vec::new();
+    let mut buf = [0; 1];
+    while decoder.read_exact(&mut buf).is_ok() {
+        data.push(buf[0]);
+    }
+    assert_eq!(data, b"foobar");
+}
+
+#[test]
+fn encoders_handle_partial_writes() {
+    let mut encoder = Base64Encoder::new(Cursor::new([0; 6]));
+    encoder.write_all(b"foo").unwrap();
+    assert!(encoder.finish().is_ok());
+    encoder.write_all(b"ba").unwrap();
+    assert_eq!(encoder.finish(), Err(CursorError::UnexpectedEndOfFile));
+    assert_eq!(encoder.get_ref().get_ref(), b"Zm9vYm");
+}
+
+#[test]
+fn empty_buffers() {
+    let mut encoder = HexEncoder::new(Cursor::new(Vec::new()));
+    assert!(matches!(encoder.write(&[]), Ok(OpRes::Empty)));
+    let mut encoder = Base64Encoder::new(Cursor::new(Vec::new()));
+    assert!(matches!(encoder.write(&[]), Ok(OpRes::Empty)));
+
+    let mut decoder = HexDecoder::new(Cursor::new(&b"6162"[..]));
+    assert!(matches!(decoder.read(&mut []), Ok(OpRes::Empty)));
+    assert_eq!(decode(decoder).unwrap(), b"ab");
+    let mut decoder = Base64Decoder::new(Cursor::new(&b"Zm8="[..]));
+    assert!(matches!(decoder.read(&mut []), Ok(OpRes::Empty)));
+    assert_eq!(decode(decoder).unwrap(), b"fo");
+}
@@ -0,0 +1,139 @@
+//! The corpus in `tests/data` is generated by `generate.py` with the zlib and
+//! gzip modules of the Python standard library.
+
+use rw_poc::{
+    Cursor, CursorError, Inflate, InflateFormat, OpRes, ReadAlloc, ReadCore, INFLATE_WINDOW_SIZE,
+};
+
+const TEXT: &[u8] = include_bytes!("data/text.txt");
+const NOISE: &[u8] = include_bytes!("data/noise.bin");
+
+fn inflate(data: &[u8], format: InflateFormat) -> Result<Vec<u8>, CursorError> {
+    let mut window = [0; INFLATE_WINDOW_SIZE];
+    let mut inflate = Inflate::new(Cursor::new(data), &mut window, format);
+    let mut out = Vec::new();
+    inflate.read_to_end(&mut out).map(|_| out)
+}
+
+#[test]
+fn raw_deflate() {
+    let data = include_bytes!("data/text.deflate");
+    assert_eq!(inflate(data, InflateFormat::Raw).unwrap(), TEXT);
+}
+
+#[test]
+fn zlib() {
+    let data = include_bytes!("data/text.zz");
+    assert_eq!(inflate(data, InflateFormat::Zlib).unwrap(), TEXT);
+    let data = include_bytes!("data/stored.zz");
+    assert_eq!(inflate(data, InflateFormat::Zlib).unwrap(), TEXT);
+    let data = include_bytes!("data/fixed.zz");
+    assert_eq!(
+        inflate(data, InflateFormat::Zlib).unwrap(),
+        b"hello hello hello hello\n"
+    );
+}
+
+#[test]
+fn gzip() {
+    let data = include_bytes!("data/text.gz");
+    assert_eq!(inflate(data, InflateFormat::Gzip).unwrap(), TEXT);
+    let data = include_bytes!("data/noise.gz");
+    assert_eq!(inflate(data, InflateFormat::Gzip).unwrap(), NOISE);
+    let data = include_bytes!("data/empty.gz");
+    assert_eq!(inflate(data, InflateFormat::Gzip).unwrap(), b"");
+}
+
+#[test]
+fn small_reads() {
+    let data = include_bytes!("data/text.gz");
+    let mut window = [0; INFLATE_WINDOW_SIZE];
+    let mut inflate = Inflate::gzip(Cursor::new(&data[..]), &mut window);
+    let mut out = Vec::new();
+    let mut buf = [0; 7];
+    assert!(matches!(inflate.read(&mut []), Ok(OpRes::Empty)));
+    loop {
+        match inflate.read(&mut buf).unwrap() {
+            OpRes::Partial(n) | OpRes::Completly(n) => out.extend_from_slice(&buf[..n.get()]),
+            OpRes::Eof => break,
+            _ => panic!("inflate did not make progress"),
+        }
+    }
+    assert_eq!(out, TEXT);
+}
+
+/// Wraps `chunks` into stored deflate blocks.
+fn stored_blocks(chunks: &[&[u8]]) -> Vec<u8> {
+    let mut data = Vec::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let len = chunk.len() as u16;
+        data.push((i + 1 == chunks.len()) as u8);
+        data.extend_from_slice(&len.to_le_bytes());
+        data.extend_from_slice(&(!len).to_le_bytes());
+        data.extend_from_slice(chunk);
+    }
+    data
+}
+
+#[test]
+fn large_stored_blocks() {
+    let text: Vec<u8> = TEXT.iter().copied().cycle().take(100_000).collect();
+    let data = stored_blocks(&[&text[..60_000], &[], &text[60_000..]]);
+    assert_eq!(inflate(&data, InflateFormat::Raw).unwrap(), text);
+
+    let mut window = [0; INFLATE_WINDOW_SIZE];
+    let mut inflate = Inflate::new(Cursor::new(&data[..]), &mut window, InflateFormat::Raw);
+    let mut buf = vec![0; 50_000];
+    let mut out = Vec::new();
+    loop {
+        match inflate.read(&mut buf).unwrap() {
+            OpRes::Partial(n) | OpRes::Completly(n) => out.extend_from_slice(&buf[..n.get()]),
+            OpRes::Eof => break,
+            _ => panic!("inflate did not make progress"),
+        }
+    }
+    assert_eq!(out, text);
+}
+
+#[test]
+fn truncated_stream() {
+    let data = include_bytes!("data/text.gz");
+    for len in [
+        0,
+        5,
+        10,
+        100,
+        data.len() / 2,
+        data.len() - 4,
+        data.len() - 1,
+    ] {
+        let res = inflate(&data[..len], InflateFormat::Gzip);
+        assert_eq!(res, Err(CursorError::UnexpectedEndOfFile), "{}", len);
+    }
+    let data = include_bytes!("data/stored.zz");
+    let res = inflate(&data[..data.len() / 2], InflateFormat::Zlib);
+    assert_eq!(res, Err(CursorError::UnexpectedEndOfFile));
+}
+
+#[test]
+fn corrupt_stream() {
+    let mut data = include_bytes!("data/text.zz").to_vec();
+    data[0] ^= 0x01;
+    let res = inflate(&data, InflateFormat::Zlib);
+    assert_eq!(res, Err(CursorError::InvalidEncoding));
+
+    let mut data = include_bytes!("data/text.gz").to_vec();
+    let len = data.len();
+    data[len - 6] ^= 0x01;
+    let res = inflate(&data, InflateFormat::Gzip);
+    assert_eq!(res, Err(CursorError::InvalidEncoding));
+
+    let mut data = include_bytes!("data/stored.zz").to_vec();
+    data[4] ^= 0x01;
+    let res = inflate(&data, InflateFormat::Zlib);
+    assert_eq!(res, Err(CursorError::InvalidEncoding));
+
+    let data = include_bytes!("data/text.zz");
+    let res = inflate(data, InflateFormat::Gzip);
+    assert_eq!(res, Err(CursorError::InvalidEncoding));
+}
@@ -0,0 +1,37 @@
+use std::io::ErrorKind;
+
+use rw_poc::{CursorError, LegacyRead, LegacyWrite, OpRes, Read, ReadCore, Write, WriteCore};
+
+/// Reports `Retry` for every operation.
+struct Interrupted;
+
+impl ReadCore for Interrupted {
+    type Err = CursorError;
+
+    fn read(&mut self, _: &mut [u8]) -> Result<OpRes, Self::Err> {
+        Ok(OpRes::Retry)
+    }
+}
+
+impl WriteCore for Interrupted {
+    type Err = CursorError;
+
+    fn write(&mut self, _: &[u8]) -> Result<OpRes, Self::Err> {
+        Ok(OpRes::Retry)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
+#[test]
+fn retry_names_the_operation() {
+    let err = Read::read(&mut LegacyRead(Interrupted), &mut [0; 4]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Interrupted);
+    assert_eq!(err.to_string(), "Read was Interrupted. Try again.");
+
+    let err = Write::write(&mut LegacyWrite(Interrupted), b"data").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Interrupted);
+    assert_eq!(err.to_string(), "Write was Interrupted. Try again.");
+}
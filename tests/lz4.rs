@@ -0,0 +1,237 @@
+//! `text.lz4` and `linked.lz4` in `tests/data` are generated by the reference
+//! implementation:
+//!
+//! ```text
+//! lz4 -9 text.txt text.lz4
+//! cat text.txt text.txt | lz4 -B4 -BD -BX --content-size - linked.lz4
+//! ```
+
+use std::num::NonZeroUsize;
+
+use rw_poc::{
+    Cursor, CursorError, DropPolicy, Lz4Reader, Lz4Writer, OpRes, ReadAlloc, ReadCore, WriteCore,
+    LZ4_WINDOW_SIZE,
+};
+
+const TEXT: &[u8] = include_bytes!("data/text.txt");
+const NOISE: &[u8] = include_bytes!("data/noise.bin");
+
+fn decompress(data: &[u8]) -> Result<Vec<u8>, CursorError> {
+    let mut window = [0; LZ4_WINDOW_SIZE];
+    let mut reader = Lz4Reader::new(Cursor::new(data), &mut window);
+    let mut out = Vec::new();
+    reader.read_to_end(&mut out).map(|_| out)
+}
+
+fn compress<const N: usize>(data: &[u8], checksum: bool, chunk: usize) -> Vec<u8> {
+    let sink = Cursor::new(Vec::new());
+    let mut writer = match checksum {
+        true => Lz4Writer::<_, N>::with_content_checksum(sink),
+        false => Lz4Writer::<_, N>::new(sink),
+    };
+    assert!(matches!(writer.write(&[]), Ok(OpRes::Empty)));
+    for chunk in data.chunks(chunk) {
+        writer.write_all(chunk).unwrap();
+    }
+    writer.finish().unwrap();
+    writer.into_inner().into_inner()
+}
+
+#[test]
+fn reference_frames() {
+    assert_eq!(decompress(include_bytes!("data/text.lz4")).unwrap(), TEXT);
+    let data = decompress(include_bytes!("data/linked.lz4")).unwrap();
+    assert_eq!(data, [TEXT, TEXT].concat());
+}
+
+#[test]
+fn round_trip() {
+    let repeated = [b'a'; 100_000];
+    for data in [
+        TEXT,
+        NOISE,
+        &repeated,
+        b"",
+        b"a",
+        b"hello hello hello hello\n",
+    ] {
+        assert_eq!(
+            decompress(&compress::<4096>(data, false, 1000)).unwrap(),
+            data
+        );
+        assert_eq!(decompress(&compress::<65536>(data, true, 7)).unwrap(), data);
+        assert_eq!(decompress(&compress::<16>(data, true, 100)).unwrap(), data);
+    }
+    let compressed = compress::<4096>(TEXT, true, 4096);
+    assert!(compressed.len() < TEXT.len() / 2);
+    let compressed = compress::<4096>(NOISE, true, 4096);
+    assert!(compressed.len() < NOISE.len() + 100);
+}
+
+#[test]
+fn concatenated_frames() {
+    let mut writer = Lz4Writer::<_, 1024>::new(Cursor::new(Vec::new()));
+    writer.write_all(b"first ").unwrap();
+    writer.flush().unwrap();
+    writer.write_all(b"block").unwrap();
+    writer.finish().unwrap();
+    writer.write_all(b", second frame").unwrap();
+    writer.finish().unwrap();
+    let data = writer.into_inner().into_inner();
+    assert_eq!(decompress(&data).unwrap(), b"first block, second frame");
+}
+
+#[test]
+fn small_reads() {
+    let data = compress::<4096>(TEXT, true, 4096);
+    let mut window = [0; LZ4_WINDOW_SIZE];
+    let mut reader = Lz4Reader::new(Cursor::new(&data[..]), &mut window);
+    let mut out = Vec::new();
+    let mut buf = [0; 3];
+    assert!(matches!(reader.read(&mut []), Ok(OpRes::Empty)));
+    loop {
+        match reader.read(&mut buf).unwrap() {
+            OpRes::Partial(n) | OpRes::Completly(n) => out.extend_from_slice(&buf[..n.get()]),
+            OpRes::Eof => break,
+            _ => panic!("Lz4Reader did not make progress"),
+        }
+    }
+    assert_eq!(out, TEXT);
+}
+
+#[test]
+fn truncated_and_corrupt_frames() {
+    let data = compress::<4096>(TEXT, true, 4096);
+    for len in [0, 3, 6, 100, data.len() / 2, data.len() - 4, data.len() - 1] {
+        let res = decompress(&data[..len]);
+        assert_eq!(res, Err(CursorError::UnexpectedEndOfFile), "{}", len);
+    }
+
+    let mut corrupt = data.clone();
+    corrupt[0] ^= 0x01;
+    assert_eq!(decompress(&corrupt), Err(CursorError::InvalidEncoding));
+    let mut corrupt = data.clone();
+    corrupt[6] ^= 0x01;
+    assert_eq!(decompress(&corrupt), Err(CursorError::InvalidEncoding));
+    let mut corrupt = data.clone();
+    let len = corrupt.len();
+    corrupt[len - 2] ^= 0x01;
+    assert_eq!(decompress(&corrupt), Err(CursorError::InvalidEncoding));
+}
+
+#[test]
+fn writer_handles_partial_writes() {
+    let mut writer = Lz4Writer::<_, 64>::new(Cursor::new([0; 8]));
+    writer.write_all(b"data").unwrap();
+    assert_eq!(writer.finish(), Err(CursorError::UnexpectedEndOfFile));
+
+    // The retried `finish` ends the frame which failed instead of a new one
+    let mut writer = Lz4Writer::<_, 64>::new(Room::default());
+    writer.write_all(b"data").unwrap();
+    writer.get_mut().room = 15;
+    assert_eq!(writer.finish(), Err(CursorError::UnexpectedEndOfFile));
+    writer.get_mut().room = 100;
+    writer.finish().unwrap();
+    let data = writer.into_inner().data;
+    assert_eq!(data.len(), 19);
+    assert_eq!(decompress(&data).unwrap(), b"data");
+}
+
+/// Accepts `room` bytes and reports `Eof` afterwards.
+#[derive(Default)]
+struct Room {
+    data: Vec<u8>,
+    room: usize,
+}
+
+impl WriteCore for Room {
+    type Err = CursorError;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        let n = buf.len().min(self.room - self.data.len());
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(match NonZeroUsize::new(n) {
+            Some(n) if n.get() == buf.len() => OpRes::Completly(n),
+            Some(n) => OpRes::Partial(n),
+            None => OpRes::Eof,
+        })
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
+/// Reports `WouldBlock` on every other call and accepts a single byte
+/// otherwise.
+#[derive(Default)]
+struct Trickle {
+    data: Vec<u8>,
+    ready: bool,
+}
+
+impl WriteCore for Trickle {
+    type Err = CursorError;
+
+    fn write(&mut self, buf: &[u8]) -> Result<OpRes, Self::Err> {
+        self.ready = !self.ready;
+        if !self.ready {
+            return Ok(OpRes::WouldBlock);
+        }
+        self.data.push(buf[0]);
+        Ok(match buf.len() {
+            1 => OpRes::Completly(NonZeroUsize::new(1).unwrap()),
+            _ => OpRes::Partial(NonZeroUsize::new(1).unwrap()),
+        })
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Err> {
+        Ok(())
+    }
+}
+
+#[test]
+fn writer_resumes_after_would_block() {
+    let mut writer = Lz4Writer::<_, 4096>::with_content_checksum(Trickle::default());
+    let mut data = TEXT;
+    while !data.is_empty() {
+        match writer.write(data) {
+            Ok(OpRes::Partial(n)) | Ok(OpRes::Completly(n)) => data = &data[n.get()..],
+            Err(CursorError::WouldBlock) => {}
+            res => panic!("unexpected result {:?}", res.err()),
+        }
+    }
+    while let Err(err) = writer.finish() {
+        assert_eq!(err, CursorError::WouldBlock);
+    }
+    let data = writer.into_inner().data;
+    assert_eq!(data, compress::<4096>(TEXT, true, TEXT.len()));
+    assert_eq!(decompress(&data).unwrap(), TEXT);
+}
+
+#[test]
+fn drop_finishes_frame() {
+    let mut out = Vec::new();
+    {
+        let mut writer = Lz4Writer::<_, 1024>::new(Cursor::new(&mut out));
+        writer.write_all(b"dropped").unwrap();
+    }
+    assert_eq!(decompress(&out).unwrap(), b"dropped");
+
+    let mut out = Vec::new();
+    {
+        let mut writer = Lz4Writer::<_, 1024>::new(Cursor::new(&mut out));
+        writer.set_drop_policy(DropPolicy::Discard);
+        writer.write_all(b"dropped").unwrap();
+    }
+    assert!(out.is_empty());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "Lz4Writer dropped without finishing the frame")]
+fn drop_without_finish_panics_in_debug() {
+    let mut writer = Lz4Writer::<_, 1024>::new(Cursor::new(Vec::new()));
+    writer.set_drop_policy(DropPolicy::PanicInDebug);
+    writer.write_all(b"data").unwrap();
+}
@@ -0,0 +1,50 @@
+use rw_poc::Error;
+use std::io;
+
+// EPERM, ENOENT, EINTR, EAGAIN, ENOMEM, EACCES, EEXIST, EINVAL, EPIPE,
+// EADDRINUSE, ECONNRESET, ETIMEDOUT, ECONNREFUSED on Linux
+const CODES: [i32; 13] = [1, 2, 4, 11, 12, 13, 17, 22, 32, 98, 104, 110, 111];
+
+#[test]
+fn kind_matches_std() {
+    for &code in CODES.iter() {
+        let err = Error::from_raw_os_error(code);
+        assert_eq!(err.raw_os_error(), Some(code));
+        assert_eq!(
+            err.kind(),
+            io::Error::from_raw_os_error(code).kind(),
+            "code {}",
+            code
+        );
+    }
+}
+
+#[test]
+fn message_matches_std() {
+    for &code in CODES.iter() {
+        assert_eq!(
+            Error::from_raw_os_error(code).to_string(),
+            io::Error::from_raw_os_error(code).to_string()
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn linux_table() {
+    let err = Error::from_raw_os_error(2);
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    assert_eq!(err.to_string(), "No such file or directory (os error 2)");
+    assert_eq!(
+        Error::from_raw_os_error(11).kind(),
+        io::ErrorKind::WouldBlock
+    );
+    assert_eq!(
+        Error::from_raw_os_error(111).kind(),
+        io::ErrorKind::ConnectionRefused
+    );
+
+    let unknown = Error::from_raw_os_error(4095);
+    assert_eq!(unknown.kind(), io::ErrorKind::Other);
+    assert_eq!(unknown.to_string(), "Unknown error (os error 4095)");
+}
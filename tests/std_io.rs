@@ -0,0 +1,232 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, ErrorKind, IoSlice, IoSliceMut, Read as _, Write as _};
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rw_poc::{
+    Cursor, FromStdIo, IntoStdIo, IoSliceCore, IoSliceMutCore, OpRes, ReadCore, WriteCore,
+};
+
+const DATA: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+/// A file in the temp directory which is unique to the test and removed on drop.
+struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    fn new() -> (Self, File) {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        loop {
+            let name = format!(
+                "rw-poc-std_io-{}-{}",
+                process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            );
+            let path = std::env::temp_dir().join(name);
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return (Self { path }, file),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => panic!("Unable to create temp file: {}", e),
+            }
+        }
+    }
+
+    fn open(&self) -> File {
+        File::open(&self.path).expect("Unable to open temp file")
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Fails every operation with an error of the given kind.
+struct Failing(ErrorKind);
+
+impl io::Read for Failing {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::new(self.0, "failing reader"))
+    }
+}
+
+impl io::Write for Failing {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(self.0, "failing writer"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn file_from_std() {
+    let (temp, file) = TempFile::new();
+    let mut writer = FromStdIo::new(file);
+    writer.write_all(DATA).unwrap();
+    writer.flush().unwrap();
+
+    let mut reader = FromStdIo::new(temp.open());
+    let mut buf = [0; DATA.len()];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf[..], DATA);
+    assert!(matches!(reader.read(&mut buf), Ok(OpRes::Eof)));
+}
+
+#[test]
+fn file_into_std() {
+    let (temp, file) = TempFile::new();
+    let mut writer = IntoStdIo::new(FromStdIo::new(file));
+    writer.write_all(DATA).unwrap();
+    writer.flush().unwrap();
+
+    let mut reader = IntoStdIo::new(FromStdIo::new(temp.open()));
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, DATA);
+}
+
+#[test]
+fn cursor_into_std() {
+    let mut writer = IntoStdIo::new(Cursor::new(Vec::new()));
+    writer.write_all(DATA).unwrap();
+    let mut reader = IntoStdIo::new(Cursor::new(writer.into_inner().into_inner()));
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, DATA);
+}
+
+#[cfg(unix)]
+#[test]
+fn socket_from_std() {
+    use std::os::unix::net::UnixStream;
+
+    let (first, second) = UnixStream::pair().unwrap();
+    let mut first = FromStdIo::new(first);
+    let mut second = FromStdIo::new(second);
+    let mut buf = [0; DATA.len()];
+
+    first.write_all(DATA).unwrap();
+    second.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf[..], DATA);
+
+    second.write_all(b"reply").unwrap();
+    first.read_exact(&mut buf[..5]).unwrap();
+    assert_eq!(&buf[..5], b"reply");
+}
+
+#[cfg(unix)]
+#[test]
+fn socket_into_std() {
+    use std::os::unix::net::UnixStream;
+
+    let (first, second) = UnixStream::pair().unwrap();
+    let mut first = IntoStdIo::new(FromStdIo::new(first));
+    let mut second = IntoStdIo::new(FromStdIo::new(second));
+    let mut buf = [0; DATA.len()];
+
+    first.write_all(DATA).unwrap();
+    second.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf[..], DATA);
+
+    second.write_all(b"reply").unwrap();
+    first.read_exact(&mut buf[..5]).unwrap();
+    assert_eq!(&buf[..5], b"reply");
+}
+
+#[test]
+fn error_kind_from_std() {
+    let mut reader = FromStdIo::new(Failing(ErrorKind::InvalidData));
+    let err = reader.read(&mut [0; 4]).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    let mut writer = FromStdIo::new(Failing(ErrorKind::PermissionDenied));
+    let err = writer.write(b"data").err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+}
+
+#[test]
+fn error_kind_into_std() {
+    let mut reader = IntoStdIo::new(Cursor::new(&b"ab"[..]));
+    let err = reader.read_exact(&mut [0; 3]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+    let mut reader = IntoStdIo::new(FromStdIo::new(Failing(ErrorKind::InvalidData)));
+    let err = reader.read(&mut [0; 4]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    let mut writer = IntoStdIo::new(FromStdIo::new(Failing(ErrorKind::PermissionDenied)));
+    let err = writer.write(b"data").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+}
+
+#[cfg(unix)]
+#[test]
+fn os_error_survives_both_conversions() {
+    use std::os::unix::net::UnixStream;
+
+    let (first, second) = UnixStream::pair().unwrap();
+    drop(second);
+    let expected = (&first).write(DATA).unwrap_err();
+    assert_eq!(expected.kind(), ErrorKind::BrokenPipe);
+
+    let mut writer = FromStdIo::new(first);
+    let err = writer.write(DATA).err().unwrap();
+    assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+    assert_eq!(err.raw_os_error(), expected.raw_os_error());
+
+    let mut writer = IntoStdIo::new(writer);
+    let err = writer.write(DATA).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+    assert_eq!(err.raw_os_error(), expected.raw_os_error());
+}
+
+#[cfg(unix)]
+#[test]
+fn would_block_survives_both_conversions() {
+    use std::os::unix::net::UnixStream;
+
+    let (first, _second) = UnixStream::pair().unwrap();
+    first.set_nonblocking(true).unwrap();
+
+    let mut reader = FromStdIo::new(first);
+    assert!(matches!(reader.read(&mut [0; 4]), Ok(OpRes::WouldBlock)));
+
+    let mut reader = IntoStdIo::new(reader);
+    let err = reader.read(&mut [0; 4]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WouldBlock);
+}
+
+#[test]
+fn vectored_slices_convert_both_ways() {
+    for count in [3, 12] {
+        let chunks: Vec<_> = DATA.chunks(DATA.len() / count + 1).collect();
+        let std_bufs: Vec<_> = chunks.iter().map(|chunk| IoSlice::new(chunk)).collect();
+        let joined = IoSliceCore::from_std_slices(&std_bufs, |bufs| {
+            IoSliceCore::as_std_slices(bufs, |bufs| {
+                bufs.iter()
+                    .flat_map(|buf| buf.iter().copied())
+                    .collect::<Vec<_>>()
+            })
+        });
+        assert_eq!(joined, DATA);
+
+        let mut out = [0; DATA.len()];
+        let mut std_bufs: Vec<_> = out
+            .chunks_mut(DATA.len() / count + 1)
+            .map(IoSliceMut::new)
+            .collect();
+        IoSliceMutCore::from_std_slices(&mut std_bufs, |bufs| {
+            IoSliceMutCore::as_std_slices(bufs, |bufs| {
+                for (buf, chunk) in bufs.iter_mut().zip(&chunks) {
+                    buf.copy_from_slice(chunk);
+                }
+            })
+        });
+        assert_eq!(&out[..], DATA);
+    }
+}
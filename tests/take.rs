@@ -0,0 +1,79 @@
+use rw_poc::{Cursor, CursorError, OpRes, ReadCore, SeekCore, SeekFrom};
+
+#[test]
+fn set_limit_keeps_origin() {
+    let mut take = Cursor::new(&b"0123456789"[..]).take(4);
+    let mut buf = [0; 2];
+    take.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"01");
+
+    take.set_limit(6);
+    assert_eq!(take.stream_position().unwrap(), 2);
+    assert_eq!(take.stream_len().unwrap(), 8);
+    assert_eq!(take.seek(SeekFrom::Start(0)).unwrap(), 0);
+    take.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"01");
+    assert_eq!(take.limit(), 6);
+}
+
+#[test]
+fn stream_len_is_clamped_to_inner() {
+    let mut inner = Cursor::new(&b"0123456789"[..]);
+    inner.seek(SeekFrom::Start(6)).unwrap();
+    let mut take = inner.take(100);
+    assert_eq!(take.stream_len().unwrap(), 4);
+    assert_eq!(take.stream_position().unwrap(), 0);
+
+    let mut buf = [0; 3];
+    take.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"678");
+    assert_eq!(take.stream_len().unwrap(), 4);
+    assert_eq!(take.stream_position().unwrap(), 3);
+}
+
+#[test]
+fn seek_outside_window() {
+    let mut take = Cursor::new(&b"0123456789"[..]).take(5);
+    assert_eq!(take.seek(SeekFrom::End(-1)).unwrap(), 4);
+    assert_eq!(
+        take.seek(SeekFrom::Current(2)),
+        Err(CursorError::OutOfBounds)
+    );
+    assert_eq!(take.seek(SeekFrom::Current(-1)).unwrap(), 3);
+    let mut buf = [0; 2];
+    take.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"34");
+}
+
+#[test]
+fn truncated_read_is_partial() {
+    let mut take = Cursor::new(&b"0123456789"[..]).take(3);
+    let mut buf = [0; 5];
+    match take.read(&mut buf).unwrap() {
+        OpRes::Partial(n) => assert_eq!(n.get(), 3),
+        _ => panic!("expected a partial read"),
+    }
+    assert_eq!(&buf[..3], b"012");
+    assert!(matches!(take.read(&mut buf).unwrap(), OpRes::Eof));
+
+    let mut take = Cursor::new(&b"0123456789"[..]).take(3);
+    let mut buf = [0; 3];
+    match take.read(&mut buf).unwrap() {
+        OpRes::Completly(n) => assert_eq!(n.get(), 3),
+        _ => panic!("expected a complete read"),
+    }
+}
+
+#[test]
+fn unbounded_limit_does_not_overflow() {
+    let mut take = Cursor::new(&b"0123456789"[..]).take(4);
+    take.read_exact(&mut [0; 1]).unwrap();
+    take.set_limit(u64::MAX);
+    assert_eq!(
+        take.seek(SeekFrom::End(0)).err(),
+        Some(CursorError::OutOfBounds)
+    );
+    assert_eq!(take.stream_position().unwrap(), 1);
+    assert_eq!(take.stream_len().unwrap(), 10);
+    assert_eq!(take.seek(SeekFrom::Start(0)).unwrap(), 0);
+}
@@ -0,0 +1,93 @@
+use rw_poc::{Cursor, CursorError, OpRes, ReadCore, Tee, TeeError, TeePolicy};
+
+const DATA: &[u8] = b"0123456789";
+const WRITE_EOF: Option<TeeError<CursorError, CursorError>> =
+    Some(TeeError::Write(CursorError::UnexpectedEndOfFile));
+
+type SmallTee = Tee<Cursor<&'static [u8]>, Cursor<[u8; 4]>>;
+
+/// Tee which mirrors into a writer that only has room for four bytes.
+fn small_tee(policy: TeePolicy) -> SmallTee {
+    let mut tee = Cursor::new(DATA).tee(Cursor::new([0; 4]));
+    tee.set_policy(policy);
+    tee
+}
+
+/// Reads three bytes at a time until the end, collecting data and errors.
+fn read_all(tee: &mut SmallTee) -> (Vec<u8>, Vec<TeeError<CursorError, CursorError>>) {
+    let mut data = Vec::new();
+    let mut errors = Vec::new();
+    let mut buf = [0; 3];
+    loop {
+        match tee.read(&mut buf) {
+            Ok(OpRes::Partial(n)) | Ok(OpRes::Completly(n)) => {
+                data.extend_from_slice(&buf[..n.get()])
+            }
+            Ok(OpRes::Eof) => break,
+            Ok(_) => unreachable!(),
+            Err(err) => errors.push(err),
+        }
+    }
+    (data, errors)
+}
+
+#[test]
+fn mirrors_everything() {
+    let mut tee = Cursor::new(DATA).tee(Cursor::new(Vec::new()));
+    let mut buf = [0; DATA.len()];
+    tee.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf[..], DATA);
+    assert_eq!(tee.writer().get_ref().as_slice(), DATA);
+    assert!(tee.take_error().is_none());
+}
+
+#[test]
+fn propagate_hands_out_data_first() {
+    let mut tee = small_tee(TeePolicy::Propagate);
+    let mut buf = [0; 3];
+    assert!(matches!(tee.read(&mut buf), Ok(OpRes::Completly(_))));
+    assert_eq!(&buf, b"012");
+    assert!(matches!(tee.read(&mut buf), Ok(OpRes::Completly(_))));
+    assert_eq!(&buf, b"345");
+    assert_eq!(tee.read(&mut buf).err(), WRITE_EOF);
+    assert!(matches!(tee.read(&mut buf), Ok(OpRes::Completly(_))));
+    assert_eq!(&buf, b"678");
+    assert_eq!(tee.get_ref().position(), 9);
+
+    let mut tee = small_tee(TeePolicy::Propagate);
+    let (data, errors) = read_all(&mut tee);
+    assert_eq!(data, DATA);
+    assert_eq!(errors.len(), 3);
+    assert_eq!(tee.writer().get_ref(), b"0123");
+}
+
+#[test]
+fn propagate_error_can_be_taken() {
+    let mut tee = small_tee(TeePolicy::Propagate);
+    let mut buf = [0; 6];
+    tee.read_exact(&mut buf).unwrap();
+    assert_eq!(tee.take_error(), Some(CursorError::UnexpectedEndOfFile));
+    assert!(matches!(tee.read(&mut buf), Ok(OpRes::Partial(_))));
+    assert_eq!(&buf[..4], b"6789");
+}
+
+#[test]
+fn ignore_keeps_reading() {
+    let mut tee = small_tee(TeePolicy::Ignore);
+    let (data, errors) = read_all(&mut tee);
+    assert_eq!(data, DATA);
+    assert!(errors.is_empty());
+    assert!(tee.take_error().is_none());
+    assert_eq!(tee.writer().get_ref(), b"0123");
+}
+
+#[test]
+fn stash_keeps_first_error() {
+    let mut tee = small_tee(TeePolicy::Stash);
+    let (data, errors) = read_all(&mut tee);
+    assert_eq!(data, DATA);
+    assert!(errors.is_empty());
+    assert_eq!(tee.take_error(), Some(CursorError::UnexpectedEndOfFile));
+    assert!(tee.take_error().is_none());
+    assert_eq!(tee.writer().get_ref(), b"0123");
+}
@@ -0,0 +1,112 @@
+use rw_poc::{Cursor, CursorError, ReadCoreExt, Signed, Unsigned, WriteCoreExt};
+
+const INVALID: Option<CursorError> = Some(CursorError::InvalidVarint);
+const EOF: Option<CursorError> = Some(CursorError::UnexpectedEndOfFile);
+
+fn uleb<T: Unsigned>(value: T) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new());
+    writer.write_uleb128(value).unwrap();
+    writer.into_inner()
+}
+
+fn sleb<T: Signed>(value: T) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new());
+    writer.write_sleb128(value).unwrap();
+    writer.into_inner()
+}
+
+fn read_uleb<T: Unsigned>(data: &[u8]) -> Result<T, CursorError> {
+    Cursor::new(data).read_uleb128()
+}
+
+fn read_sleb<T: Signed>(data: &[u8]) -> Result<T, CursorError> {
+    Cursor::new(data).read_sleb128()
+}
+
+#[test]
+fn unsigned_round_trip() {
+    assert_eq!(uleb(0u8), [0x00]);
+    assert_eq!(uleb(127u8), [0x7f]);
+    assert_eq!(uleb(128u8), [0x80, 0x01]);
+    assert_eq!(uleb(624_485u32), [0xe5, 0x8e, 0x26]);
+    assert_eq!(uleb(u64::MAX).len(), 10);
+    assert_eq!(uleb(u128::MAX).len(), 19);
+
+    for value in [0, 1, 127, 128, u64::from(u32::MAX), u64::MAX - 1, u64::MAX] {
+        assert_eq!(read_uleb::<u64>(&uleb(value)), Ok(value));
+    }
+    for value in [0, u8::MAX] {
+        assert_eq!(read_uleb::<u8>(&uleb(value)), Ok(value));
+    }
+    assert_eq!(read_uleb::<u16>(&uleb(u16::MAX)), Ok(u16::MAX));
+    assert_eq!(read_uleb::<u32>(&uleb(u32::MAX)), Ok(u32::MAX));
+    assert_eq!(read_uleb::<u128>(&uleb(u128::MAX)), Ok(u128::MAX));
+}
+
+#[test]
+fn signed_round_trip() {
+    assert_eq!(sleb(0i8), [0x00]);
+    assert_eq!(sleb(-1i8), [0x7f]);
+    assert_eq!(sleb(63i8), [0x3f]);
+    assert_eq!(sleb(64i8), [0xc0, 0x00]);
+    assert_eq!(sleb(-64i8), [0x40]);
+    assert_eq!(sleb(-65i8), [0xbf, 0x7f]);
+    assert_eq!(sleb(-123_456i32), [0xc0, 0xbb, 0x78]);
+    assert_eq!(sleb(i64::MIN).len(), 10);
+    assert_eq!(sleb(i128::MIN).len(), 19);
+
+    for value in [0, -1, 63, 64, -64, -65, i64::MIN, i64::MIN + 1, i64::MAX] {
+        assert_eq!(read_sleb::<i64>(&sleb(value)), Ok(value));
+    }
+    for value in [i8::MIN, -1, 0, i8::MAX] {
+        assert_eq!(read_sleb::<i8>(&sleb(value)), Ok(value));
+    }
+    assert_eq!(read_sleb::<i32>(&sleb(i32::MIN)), Ok(i32::MIN));
+    assert_eq!(read_sleb::<i128>(&sleb(i128::MIN)), Ok(i128::MIN));
+    assert_eq!(read_sleb::<i128>(&sleb(i128::MAX)), Ok(i128::MAX));
+}
+
+#[test]
+fn overlong_encodings() {
+    assert_eq!(read_uleb::<u32>(&[0x80, 0x00]).err(), INVALID);
+    assert_eq!(read_uleb::<u32>(&[0x81, 0x80, 0x00]).err(), INVALID);
+    assert_eq!(read_uleb::<u128>(&[0x80; 20]).err(), INVALID);
+    assert_eq!(read_sleb::<i32>(&[0x80, 0x00]).err(), INVALID);
+    assert_eq!(read_sleb::<i128>(&[0x80; 20]).err(), INVALID);
+    assert_eq!(read_sleb::<i32>(&[0xff, 0x7f]).err(), INVALID);
+    assert_eq!(read_sleb::<i32>(&[0xc0, 0xff, 0x7f]).err(), INVALID);
+}
+
+#[test]
+fn overflow() {
+    assert_eq!(
+        read_uleb::<u64>(&uleb(u128::from(u64::MAX) + 1)).err(),
+        INVALID
+    );
+    let mut max = [0xff; 10];
+    max[9] = 0x01;
+    assert_eq!(read_uleb::<u64>(&max), Ok(u64::MAX));
+    max[9] = 0x02;
+    assert_eq!(read_uleb::<u64>(&max).err(), INVALID);
+    assert_eq!(read_uleb::<u8>(&uleb(256u16)).err(), INVALID);
+
+    assert_eq!(
+        read_sleb::<i64>(&sleb(i128::from(i64::MAX) + 1)).err(),
+        INVALID
+    );
+    assert_eq!(
+        read_sleb::<i64>(&sleb(i128::from(i64::MIN) - 1)).err(),
+        INVALID
+    );
+    assert_eq!(read_sleb::<i8>(&sleb(128i16)).err(), INVALID);
+    assert_eq!(read_sleb::<i8>(&sleb(-129i16)).err(), INVALID);
+}
+
+#[test]
+fn truncated() {
+    assert_eq!(read_uleb::<u64>(&[]).err(), EOF);
+    assert_eq!(read_uleb::<u64>(&[0x80]).err(), EOF);
+    assert_eq!(read_uleb::<u64>(&uleb(u64::MAX)[..9]).err(), EOF);
+    assert_eq!(read_sleb::<i64>(&[0xff]).err(), EOF);
+    assert_eq!(read_sleb::<i64>(&sleb(i64::MIN)[..9]).err(), EOF);
+}